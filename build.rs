@@ -0,0 +1,240 @@
+// Generates the lookup tables into $OUT_DIR/lookup.rs at build time, which
+// src/dmath/lookup.rs includes.
+//
+// Everything is computed with integer arithmetic only, on signed values with
+// 124 fractional bits, so the tables are identical whatever the host float
+// implementation. Cargo features select the table size and which tables
+// are generated.
+
+use std::env;
+use std::fs;
+use std::path::Path;
+
+const BITS: u32 = 124;
+const ONE: i128 = 1 << BITS;
+
+const SIZES: [usize; 6] = [256, 512, 1024, 2048, 4096, 8192];
+const DEFAULT_SIZE: usize = 2048;
+
+fn feature(name: &str) -> bool {
+    let key = format!("CARGO_FEATURE_{}", name.to_uppercase().replace('-', "_"));
+    env::var_os(key).is_some()
+}
+
+// Product of two values below 2^126 in magnitude, through a 256 bit
+// intermediate
+fn mul(a: i128, b: i128) -> i128 {
+    let negative = (a < 0) != (b < 0);
+    let (a, b) = (a.unsigned_abs(), b.unsigned_abs());
+    let mask = u64::MAX as u128;
+    let (a_high, a_low) = (a >> 64, a & mask);
+    let (b_high, b_low) = (b >> 64, b & mask);
+
+    let low = a_low * b_low;
+    let middle = a_high * b_low + a_low * b_high;
+    let (low, carry) = low.overflowing_add(middle << 64);
+    let high = a_high * b_high + (middle >> 64) + carry as u128;
+
+    let result = ((high << (128 - BITS)) | (low >> BITS)) as i128;
+    if negative { -result } else { result }
+}
+
+fn div(a: i128, b: i128) -> i128 {
+    div_to(a, b, BITS)
+}
+
+// Quotient with `bits` fractional bits, by long division of the widened
+// dividend
+fn div_to(a: i128, b: i128, bits: u32) -> i128 {
+    let negative = (a < 0) != (b < 0);
+    let (a, b) = (a.unsigned_abs(), b.unsigned_abs());
+    let mut remainder: u128 = 0;
+    let mut quotient: u128 = 0;
+    for i in (0..128 + bits).rev() {
+        let bit = if i >= bits { (a >> (i - bits)) & 1 } else { 0 };
+        remainder = (remainder << 1) | bit;
+        if remainder >= b {
+            remainder -= b;
+            quotient |= 1 << i;
+        }
+    }
+    let result = quotient as i128;
+    if negative { -result } else { result }
+}
+
+fn sqrt(value: i128) -> i128 {
+    if value == 0 {
+        return 0;
+    }
+    let mut root = if value > ONE { value } else { ONE };
+    loop {
+        let next = (root + div(value, root)) / 2;
+        if next >= root {
+            return root;
+        }
+        root = next;
+    }
+}
+
+// Sum of the alternating or plain series term_k / (2k + 1), where each term
+// is the previous one multiplied by `step`
+fn odd_series(first: i128, step: impl Fn(i128) -> i128, alternating: bool) -> i128 {
+    let mut sum = 0;
+    let mut term = first;
+    let mut k = 0;
+    while term != 0 {
+        let value = term / (2 * k + 1);
+        sum += if alternating && k % 2 == 1 { -value } else { value };
+        term = step(term);
+        k += 1;
+    }
+    sum
+}
+
+fn pi() -> i128 {
+    // Machin: PI = 16 atan(1/5) - 4 atan(1/239)
+    let atan_inverse = |n: i128| odd_series(ONE / n, |t| t / (n * n), true);
+    16 * atan_inverse(5) - 4 * atan_inverse(239)
+}
+
+fn ln_2() -> i128 {
+    // ln 2 = 2 atanh(1/3)
+    2 * odd_series(ONE / 3, |t| t / 9, false)
+}
+
+// ln(1 + u) = 2 atanh(u / (2 + u))
+fn ln_1p(u: i128) -> i128 {
+    let z = div(u, 2 * ONE + u);
+    let square = mul(z, z);
+    2 * odd_series(z, |t| mul(t, square), false)
+}
+
+fn exp(y: i128) -> i128 {
+    let mut sum = 0;
+    let mut term = ONE;
+    let mut k = 1;
+    while term != 0 {
+        sum += term;
+        term = mul(term, y) / k;
+        k += 1;
+    }
+    sum
+}
+
+// Taylor series, the k-th term is angle^k / k!
+fn sin_cos(angle: i128) -> (i128, i128) {
+    let mut sin = 0;
+    let mut cos = 0;
+    let mut term = ONE;
+    let mut k = 0;
+    while term != 0 {
+        let signed = if k % 4 < 2 { term } else { -term };
+        if k % 2 == 0 {
+            cos += signed;
+        } else {
+            sin += signed;
+        }
+        term = mul(term, angle) / (k + 1);
+        k += 1;
+    }
+    (sin, cos)
+}
+
+fn atan(value: i128, pi: i128) -> i128 {
+    if value > ONE {
+        return pi / 2 - atan(div(ONE, value), pi);
+    }
+    // Halve the angle twice, atan(x) = 2 atan(x / (1 + sqrt(1 + x^2)))
+    let mut x = value;
+    for _ in 0..2 {
+        x = div(x, ONE + sqrt(ONE + mul(x, x)));
+    }
+    let square = mul(x, x);
+    4 * odd_series(x, |t| mul(t, square), true)
+}
+
+fn round_to(value: i128, bits: u32) -> i64 {
+    let shift = BITS - bits;
+    ((value + (1 << (shift - 1))) >> shift) as i64
+}
+
+fn write_table(out: &mut String, comment: &str, name: &str, values: &[i64]) {
+    out.push_str(&format!("\n// {}\npub static {}: [i64; {}] = [\n", comment, name, values.len()));
+    for row in values.chunks(8) {
+        let row: Vec<String> = row.iter().map(|v| v.to_string()).collect();
+        out.push_str(&format!("    {},\n", row.join(", ")));
+    }
+    out.push_str("];\n");
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+
+    let size = SIZES.iter().rev().cloned()
+        .find(|size| feature(&format!("lut-{}", size)))
+        .unwrap_or(DEFAULT_SIZE);
+    let n = size as i128;
+
+    let pi = pi();
+    let half_pi = pi / 2;
+
+    let mut out = String::new();
+    out.push_str("// Generated by build.rs, do not edit\n\n");
+    if ["lut-sin", "lut-tan", "lut-atan", "lut-asin"].iter().any(|name| feature(name)) {
+        out.push_str(&format!("pub const LUT_SIZE: usize = {};\n", size));
+    }
+
+    if feature("lut-sin") {
+        let values: Vec<i64> = (0..n).map(|i| round_to(sin_cos(half_pi / n * i).0, 30)).collect();
+        write_table(&mut out, "sin(i * PI / 2 / LUT_SIZE) with 30 fractional bits", "SIN_LUT", &values);
+    }
+
+    if feature("lut-tan") {
+        let values: Vec<i64> = (0..n).map(|i| {
+            let (sin, cos) = sin_cos(half_pi / n * i);
+            // Too large for the working precision near PI/2
+            (div_to(sin, cos, 31) + 1) >> 1
+        }).map(|tan| tan as i64).collect();
+        write_table(&mut out, "tan(i * PI / 2 / LUT_SIZE) with 30 fractional bits", "TAN_LUT", &values);
+    }
+
+    if feature("lut-atan") {
+        let values: Vec<i64> = (0..=n).map(|i| round_to(atan(ONE / n * i, pi), 30)).collect();
+        write_table(&mut out, "atan(i / LUT_SIZE) with 30 fractional bits", "ATAN_LUT", &values);
+    }
+
+    if feature("lut-asin") {
+        let sqrt_half = sqrt(ONE / 2);
+        let values: Vec<i64> = (0..=n).map(|i| {
+            let x = sqrt_half / n * i;
+            round_to(atan(div(x, sqrt(ONE - mul(x, x))), pi), 30)
+        }).collect();
+        write_table(&mut out, "asin(i / LUT_SIZE * sqrt(1/2)) with 30 fractional bits", "ASIN_LUT", &values);
+    }
+
+    let ln_2 = ln_2();
+
+    if feature("lut-exp") {
+        let values: Vec<i64> = (1..=62).map(|i| round_to(exp(ln_2 >> i), 62)).collect();
+        write_table(&mut out, "2^(2^-(i + 1)) with 62 fractional bits", "EXP2_LUT", &values);
+    }
+
+    if feature("lut-log") {
+        let values: Vec<i64> = (1..=62).map(|i| round_to(div(ln_1p(ONE >> i), ln_2), 62)).collect();
+        write_table(&mut out, "log2(1 + 2^-(i + 1)) with 62 fractional bits", "LOG2_LUT", &values);
+    }
+
+    // The CORDIC angles are small and always present, atan2 uses them
+    let values: Vec<i64> = (0..63).map(|i| round_to(atan(ONE >> i, pi), 62)).collect();
+    write_table(&mut out, "atan(2^-i) with 62 fractional bits, the CORDIC rotation angles", "ATAN_CORDIC_LUT", &values);
+
+    // Sine and cosine only rotate with CORDIC without their table
+    if !feature("lut-sin") {
+        let gain = (0..63).fold(ONE, |gain, i| mul(gain, div(ONE, sqrt(ONE + (ONE >> (2 * i))))));
+        out.push_str("\n// Product of cos(atan(2^-i)) over the CORDIC iterations\n");
+        out.push_str(&format!("pub const CORDIC_GAIN: i64 = {};\n", round_to(gain, 62)));
+    }
+
+    let path = Path::new(&env::var("OUT_DIR").unwrap()).join("lookup.rs");
+    fs::write(path, out).unwrap();
+}
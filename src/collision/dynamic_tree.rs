@@ -0,0 +1,597 @@
+use crate::dmath::aabb::Aabb;
+use crate::dmath::fix::Fix;
+use crate::dmath::vec2::Vec2;
+
+use super::handle::{Handle, Slots};
+
+// Broadphase over a bounding volume hierarchy that is updated as entries
+// come, go and move, suited to scenes where sizes vary too much for a
+// grid.
+//
+// Leaves hold fat boxes, the entry's box grown by a margin and stretched
+// along its displacement, so an entry moving a little stays in its leaf.
+// A new leaf goes next to the node that grows the total perimeter of the
+// tree the least, and rotations on the way back up keep the heights of
+// siblings within one of each other.
+//
+// Fat boxes only steer the search. Queries and pairs test the boxes the
+// entries were given, so results match the spatial hash. Nodes are reused
+// from a free list and every choice takes the first child on ties, so the
+// same calls build the same tree on every peer, and results are sorted by
+// handle regardless.
+
+// Moving entries have their fat boxes stretched this many times their
+// displacement ahead
+const DISPLACEMENT_MULTIPLIER: Fix = Fix::TWO;
+
+// A fat box grown this many margins past the needed one is shrunk again,
+// so entries that stop do not keep boxes built for a fast move
+const SHRINK_MARGINS: Fix = Fix::from_bits(4 << 30);
+
+#[derive(Debug, Clone)]
+pub struct DynamicTree {
+    margin: Fix,
+    nodes: Vec<Node>,
+    free_nodes: Vec<usize>,
+    root: Option<usize>,
+    proxies: Slots<Proxy>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Node {
+    aabb: Aabb,
+    parent: Option<usize>,
+    children: [usize; 2],
+    // Zero for leaves
+    height: u32,
+    // Set for leaves
+    handle: Option<Handle>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Proxy {
+    aabb: Aabb,
+    leaf: usize,
+}
+
+impl DynamicTree {
+    pub fn new(margin: Fix) -> DynamicTree {
+        assert!(margin >= Fix::ZERO, "margin must not be negative");
+        DynamicTree { margin, nodes: Vec::new(), free_nodes: Vec::new(), root: None, proxies: Slots::new() }
+    }
+
+    pub fn margin(&self) -> Fix {
+        self.margin
+    }
+
+    pub fn len(&self) -> usize {
+        self.proxies.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // Zero for an empty tree or a single entry
+    pub fn height(&self) -> u32 {
+        self.root.map_or(0, |root| self.nodes[root].height)
+    }
+
+    pub fn insert(&mut self, aabb: Aabb) -> Handle {
+        let leaf = self.allocate(Node {
+            aabb: grow(aabb, self.margin),
+            parent: None,
+            children: [0, 0],
+            height: 0,
+            handle: None,
+        });
+        let handle = self.proxies.insert(Proxy { aabb, leaf });
+        self.nodes[leaf].handle = Some(handle);
+        self.insert_leaf(leaf);
+        handle
+    }
+
+    // The box the entry had, None for unknown handles
+    pub fn remove(&mut self, handle: Handle) -> Option<Aabb> {
+        let proxy = self.proxies.remove(handle)?;
+        self.remove_leaf(proxy.leaf);
+        self.free_nodes.push(proxy.leaf);
+        Some(proxy.aabb)
+    }
+
+    // Gives the entry a new box after moving by the displacement. Returns
+    // whether its fat box had to be rebuilt, which is false for unknown
+    // handles.
+    pub fn move_to(&mut self, handle: Handle, aabb: Aabb, displacement: Vec2) -> bool {
+        let Some(proxy) = self.proxies.get_mut(handle) else {
+            return false;
+        };
+        proxy.aabb = aabb;
+        let leaf = proxy.leaf;
+
+        // Saturating, a huge displacement only stretches the box to the
+        // edge of the range
+        let ahead = Vec2::new(
+            displacement.x.saturating_mul(DISPLACEMENT_MULTIPLIER),
+            displacement.y.saturating_mul(DISPLACEMENT_MULTIPLIER),
+        );
+        let mut fat = grow(aabb, self.margin);
+        let (back, forward) = (ahead.min(Vec2::ZERO), ahead.max(Vec2::ZERO));
+        fat.min = Vec2::new(fat.min.x.saturating_add(back.x), fat.min.y.saturating_add(back.y));
+        fat.max = Vec2::new(fat.max.x.saturating_add(forward.x), fat.max.y.saturating_add(forward.y));
+
+        let current = self.nodes[leaf].aabb;
+        if current.contains(aabb) && grow(fat, self.margin.saturating_mul(SHRINK_MARGINS)).contains(current) {
+            return false;
+        }
+        self.remove_leaf(leaf);
+        self.nodes[leaf].aabb = fat;
+        self.insert_leaf(leaf);
+        true
+    }
+
+    pub fn aabb(&self, handle: Handle) -> Option<Aabb> {
+        self.proxies.get(handle).map(|proxy| proxy.aabb)
+    }
+
+    pub fn fat_aabb(&self, handle: Handle) -> Option<Aabb> {
+        self.proxies.get(handle).map(|proxy| self.nodes[proxy.leaf].aabb)
+    }
+
+    // Entries whose boxes overlap or touch the box, sorted by handle
+    pub fn query_aabb(&self, aabb: Aabb) -> Vec<Handle> {
+        self.query(|bounds| bounds.intersects(aabb))
+    }
+
+    // Entries whose boxes are within the radius of the center, sorted by
+    // handle
+    pub fn query_radius(&self, center: Vec2, radius: Fix) -> Vec<Handle> {
+        let reach = radius.to_bits().unsigned_abs() as u128;
+        self.query(|bounds| (bounds.closest_point(center) - center).length_squared_wide() <= reach * reach)
+    }
+
+    // Entries hit by the ray origin + t * direction for t from zero to
+    // max_t, with the t where the ray enters each box. Sorted by t, then
+    // by handle.
+    pub fn raycast(&self, origin: Vec2, direction: Vec2, max_t: Fix) -> Vec<(Handle, Fix)> {
+        let hit = |bounds: Aabb| bounds.ray_intersection(origin, direction).filter(|(enter, _)| *enter <= max_t);
+        let mut hits: Vec<(Handle, Fix)> = self.query(|bounds| hit(bounds).is_some()).into_iter()
+            .map(|handle| (handle, hit(self.proxies.get(handle).unwrap().aabb).unwrap().0))
+            .collect();
+        hits.sort_by_key(|(handle, t)| (*t, *handle));
+        hits
+    }
+
+    // Every pair of entries whose boxes overlap or touch, with the lower
+    // handle first, sorted
+    pub fn pairs(&self) -> Vec<(Handle, Handle)> {
+        let mut pairs = Vec::new();
+        for (handle, proxy) in self.proxies.iter() {
+            let aabb = proxy.aabb;
+            let others = self.query(|bounds| bounds.intersects(aabb));
+            pairs.extend(others.into_iter().filter(|other| *other > handle).map(|other| (handle, other)));
+        }
+        pairs
+    }
+
+    // Entries whose boxes pass the test, walking down through the fat
+    // boxes that pass it. The test must pass for a box whenever it passes
+    // for a box inside it.
+    fn query(&self, test: impl Fn(Aabb) -> bool) -> Vec<Handle> {
+        let mut found = Vec::new();
+        let mut stack: Vec<usize> = self.root.into_iter().collect();
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            if !test(node.aabb) {
+                continue;
+            }
+            match node.handle {
+                Some(handle) => {
+                    if test(self.proxies.get(handle).unwrap().aabb) {
+                        found.push(handle);
+                    }
+                }
+                None => stack.extend_from_slice(&node.children),
+            }
+        }
+        found.sort();
+        found
+    }
+
+    fn allocate(&mut self, node: Node) -> usize {
+        match self.free_nodes.pop() {
+            Some(index) => {
+                self.nodes[index] = node;
+                index
+            }
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        }
+    }
+
+    fn is_leaf(&self, index: usize) -> bool {
+        self.nodes[index].handle.is_some()
+    }
+
+    fn insert_leaf(&mut self, leaf: usize) {
+        let Some(root) = self.root else {
+            self.root = Some(leaf);
+            self.nodes[leaf].parent = None;
+            return;
+        };
+
+        // Walk down to the sibling that costs the least perimeter, where
+        // every node on the way grows to hold the new leaf
+        let aabb = self.nodes[leaf].aabb;
+        let mut index = root;
+        while !self.is_leaf(index) {
+            let node = self.nodes[index];
+            let perimeter = node.aabb.perimeter();
+            let combined = node.aabb.union(aabb).perimeter();
+            // Making a new parent here
+            let cost = combined * Fix::TWO;
+            // What the nodes above have to grow in any case
+            let inherited = (combined - perimeter) * Fix::TWO;
+            let child_cost = |child: usize| {
+                let child = &self.nodes[child];
+                let grown = child.aabb.union(aabb).perimeter();
+                let growth = if child.handle.is_some() { grown } else { grown - child.aabb.perimeter() };
+                growth + inherited
+            };
+            let costs = node.children.map(child_cost);
+            if cost < costs[0] && cost < costs[1] {
+                break;
+            }
+            index = if costs[0] <= costs[1] { node.children[0] } else { node.children[1] };
+        }
+
+        let sibling = index;
+        let parent = self.nodes[sibling].parent;
+        let new_parent = self.allocate(Node {
+            aabb: self.nodes[sibling].aabb.union(aabb),
+            parent,
+            children: [sibling, leaf],
+            height: self.nodes[sibling].height + 1,
+            handle: None,
+        });
+        match parent {
+            Some(parent) => self.replace_child(parent, sibling, new_parent),
+            None => self.root = Some(new_parent),
+        }
+        self.nodes[sibling].parent = Some(new_parent);
+        self.nodes[leaf].parent = Some(new_parent);
+
+        self.refit(Some(new_parent));
+    }
+
+    fn remove_leaf(&mut self, leaf: usize) {
+        if self.root == Some(leaf) {
+            self.root = None;
+            return;
+        }
+        let parent = self.nodes[leaf].parent.unwrap();
+        let grandparent = self.nodes[parent].parent;
+        let children = self.nodes[parent].children;
+        let sibling = if children[0] == leaf { children[1] } else { children[0] };
+
+        self.nodes[sibling].parent = grandparent;
+        self.free_nodes.push(parent);
+        match grandparent {
+            Some(grandparent) => {
+                self.replace_child(grandparent, parent, sibling);
+                self.refit(Some(grandparent));
+            }
+            None => self.root = Some(sibling),
+        }
+    }
+
+    fn replace_child(&mut self, parent: usize, old: usize, new: usize) {
+        let children = &mut self.nodes[parent].children;
+        if children[0] == old { children[0] = new } else { children[1] = new }
+    }
+
+    // Rebalances and recomputes the boxes and heights from the node up to
+    // the root
+    fn refit(&mut self, mut index: Option<usize>) {
+        while let Some(current) = index {
+            let current = self.balance(current);
+            self.update(current);
+            index = self.nodes[current].parent;
+        }
+    }
+
+    fn update(&mut self, index: usize) {
+        let [first, second] = self.nodes[index].children;
+        let (first, second) = (self.nodes[first], self.nodes[second]);
+        let node = &mut self.nodes[index];
+        node.aabb = first.aabb.union(second.aabb);
+        node.height = first.height.max(second.height) + 1;
+    }
+
+    // Rotates the taller child of the node up when it is more than one
+    // level taller than the other. Returns the node now in its place.
+    fn balance(&mut self, a: usize) -> usize {
+        if self.is_leaf(a) || self.nodes[a].height < 2 {
+            return a;
+        }
+        let children = self.nodes[a].children;
+        let heights = children.map(|child| self.nodes[child].height as i64);
+        let side = match heights[1] - heights[0] {
+            balance if balance > 1 => 1,
+            balance if balance < -1 => 0,
+            _ => return a,
+        };
+
+        // c takes the place of a, a becomes the first child of c and the
+        // lower child of c goes to a
+        let c = children[side];
+        let [f, g] = self.nodes[c].children;
+        let (higher, lower) = if self.nodes[f].height > self.nodes[g].height { (f, g) } else { (g, f) };
+
+        let parent = self.nodes[a].parent;
+        self.nodes[c].parent = parent;
+        match parent {
+            Some(parent) => self.replace_child(parent, a, c),
+            None => self.root = Some(c),
+        }
+        self.nodes[c].children = [a, higher];
+        self.nodes[a].parent = Some(c);
+        self.nodes[a].children[side] = lower;
+        self.nodes[lower].parent = Some(a);
+
+        self.update(a);
+        self.update(c);
+        c
+    }
+}
+
+// Aabb::expand that stops at the edge of the range
+fn grow(aabb: Aabb, margin: Fix) -> Aabb {
+    Aabb::new(
+        Vec2::new(aabb.min.x.saturating_sub(margin), aabb.min.y.saturating_sub(margin)),
+        Vec2::new(aabb.max.x.saturating_add(margin), aabb.max.y.saturating_add(margin)),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(x: i64, y: i64) -> Vec2 {
+        Vec2::new(Fix::new(x), Fix::new(y))
+    }
+
+    fn square(x: i64, y: i64, size: i64) -> Aabb {
+        Aabb::new(at(x, y), at(x + size, y + size))
+    }
+
+    // Small deterministic generator so the test needs no dependencies
+    struct Random(u64);
+
+    impl Random {
+        fn below(&mut self, limit: i64) -> i64 {
+            self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            ((self.0 >> 33) % limit as u64) as i64
+        }
+    }
+
+    // Checks links, boxes and heights of every node below the root and
+    // returns the largest difference in height between two siblings
+    fn check(tree: &DynamicTree) -> u32 {
+        let Some(root) = tree.root else {
+            assert!(tree.is_empty());
+            return 0;
+        };
+        assert_eq!(tree.nodes[root].parent, None);
+        let mut leaves = 0;
+        let mut imbalance = 0;
+        let mut stack = vec![root];
+        while let Some(index) = stack.pop() {
+            let node = tree.nodes[index];
+            if let Some(handle) = node.handle {
+                let proxy = tree.proxies.get(handle).unwrap();
+                assert_eq!(proxy.leaf, index);
+                assert!(node.aabb.contains(proxy.aabb));
+                assert_eq!(node.height, 0);
+                leaves += 1;
+                continue;
+            }
+            let [first, second] = node.children.map(|child| tree.nodes[child]);
+            for child in node.children {
+                assert_eq!(tree.nodes[child].parent, Some(index));
+            }
+            assert_eq!(node.aabb, first.aabb.union(second.aabb));
+            assert_eq!(node.height, first.height.max(second.height) + 1);
+            imbalance = imbalance.max(first.height.abs_diff(second.height));
+            stack.extend_from_slice(&node.children);
+        }
+        assert_eq!(leaves, tree.len());
+        imbalance
+    }
+
+    fn brute_pairs(tree: &DynamicTree, handles: &[Handle]) -> Vec<(Handle, Handle)> {
+        let mut pairs = Vec::new();
+        for (i, a) in handles.iter().enumerate() {
+            for b in handles[i + 1..].iter() {
+                if tree.aabb(*a).unwrap().intersects(tree.aabb(*b).unwrap()) {
+                    pairs.push((*a.min(b), *a.max(b)));
+                }
+            }
+        }
+        pairs.sort();
+        pairs
+    }
+
+    // Inserting in order along a line would build a list without the
+    // rotations
+    #[test]
+    fn rotations_keep_sorted_inserts_balanced() {
+        let mut tree = DynamicTree::new(Fix::ZERO);
+        let handles: Vec<Handle> = (0..1024).map(|i| tree.insert(square(i * 2, 0, 1))).collect();
+        assert!(check(&tree) <= 1);
+        assert!(tree.height() <= 11, "height {}", tree.height());
+        for handle in handles.iter().step_by(2) {
+            tree.remove(*handle);
+        }
+        assert!(check(&tree) <= 1);
+        assert!(tree.height() <= 10, "height {}", tree.height());
+        for handle in handles.iter().skip(1).step_by(2) {
+            tree.remove(*handle);
+        }
+        assert_eq!(check(&tree), 0);
+        assert_eq!(tree.height(), 0);
+        assert!(tree.is_empty());
+    }
+
+    // Queries and pairs against a scan over every entry while entries
+    // come, go and move
+    #[test]
+    fn random_edits_match_a_full_scan() {
+        let mut random = Random(7);
+        let mut tree = DynamicTree::new(Fix::HALF);
+        let mut handles = Vec::new();
+        for step in 0..3000 {
+            match random.below(4) {
+                0 if !handles.is_empty() => {
+                    let handle = handles.swap_remove(random.below(handles.len() as i64) as usize);
+                    assert!(tree.remove(handle).is_some());
+                }
+                1 if !handles.is_empty() => {
+                    let handle = handles[random.below(handles.len() as i64) as usize];
+                    let aabb = square(random.below(200), random.below(200), 1 + random.below(8));
+                    let displacement = aabb.min - tree.aabb(handle).unwrap().min;
+                    tree.move_to(handle, aabb, displacement);
+                }
+                _ => handles.push(tree.insert(square(random.below(200), random.below(200), 1 + random.below(20)))),
+            }
+            if step % 100 != 0 {
+                continue;
+            }
+            assert!(check(&tree) <= 1);
+            assert_eq!(tree.pairs(), brute_pairs(&tree, &handles));
+            let area = square(random.below(200), random.below(200), 30);
+            let mut inside: Vec<Handle> =
+                handles.iter().copied().filter(|handle| tree.aabb(*handle).unwrap().intersects(area)).collect();
+            inside.sort();
+            assert_eq!(tree.query_aabb(area), inside);
+        }
+    }
+
+    #[test]
+    fn small_moves_keep_the_fat_box() {
+        let mut tree = DynamicTree::new(Fix::ONE);
+        let handle = tree.insert(square(0, 0, 2));
+        let other = tree.insert(square(10, 0, 2));
+        assert_eq!(tree.fat_aabb(handle), Some(Aabb::new(at(-1, -1), at(3, 3))));
+
+        // Still inside the margin
+        assert!(!tree.move_to(handle, Aabb::new(at(0, 0), Vec2::new(Fix::from(2.5), Fix::TWO)), Vec2::ZERO));
+        assert_eq!(tree.fat_aabb(handle), Some(Aabb::new(at(-1, -1), at(3, 3))));
+        assert_eq!(tree.aabb(handle), Some(Aabb::new(at(0, 0), Vec2::new(Fix::from(2.5), Fix::TWO))));
+
+        // Out of the fat box, rebuilt around the new box and stretched
+        // twice the displacement ahead
+        assert!(tree.move_to(handle, square(2, 0, 2), at(2, 0)));
+        assert_eq!(tree.fat_aabb(handle), Some(Aabb::new(at(1, -1), at(9, 3))));
+        assert!(!tree.move_to(handle, square(4, 0, 2), at(2, 0)));
+        assert_eq!(tree.pairs(), vec![]);
+        assert!(tree.move_to(handle, square(9, 0, 2), at(5, 0)));
+        assert_eq!(tree.pairs(), vec![(handle, other)]);
+
+        // A fat box far bigger than needed is shrunk once the entry stops
+        assert!(tree.move_to(handle, square(0, 0, 2), at(-40, 0)));
+        assert_eq!(tree.fat_aabb(handle), Some(Aabb::new(at(-81, -1), at(3, 3))));
+        assert!(tree.move_to(handle, square(0, 0, 2), Vec2::ZERO));
+        assert_eq!(tree.fat_aabb(handle), Some(Aabb::new(at(-1, -1), at(3, 3))));
+        assert!(check(&tree) <= 1);
+    }
+
+    #[test]
+    fn huge_displacements_saturate() {
+        let mut tree = DynamicTree::new(Fix::ONE);
+        let handle = tree.insert(square(0, 0, 1));
+        assert!(tree.move_to(handle, square(2, 0, 1), Vec2::new(Fix::MAX, Fix::MIN)));
+        let fat = tree.fat_aabb(handle).unwrap();
+        assert_eq!((fat.min.y, fat.max.x), (Fix::MIN, Fix::MAX));
+        assert!(!tree.move_to(handle, square(3, 0, 1), Vec2::new(Fix::MAX, Fix::MIN)));
+
+        // A margin as wide as the range gives the whole range
+        let mut tree = DynamicTree::new(Fix::MAX);
+        let handle = tree.insert(square(0, 0, 1));
+        assert!(!tree.move_to(handle, square(2, 0, 1), Vec2::new(Fix::MIN, Fix::MAX)));
+        let wide = Vec2::new(Fix::MAX, Fix::MAX);
+        assert_eq!(tree.fat_aabb(handle), Some(Aabb::new(-wide, wide)));
+    }
+
+    #[test]
+    fn raycast_sorts_by_entry_then_handle() {
+        let mut tree = DynamicTree::new(Fix::ONE);
+        let far = tree.insert(square(20, -1, 2));
+        let near = tree.insert(square(5, 0, 2));
+        let twin = tree.insert(square(5, -1, 2));
+        let behind = tree.insert(square(-10, 0, 2));
+        let around = tree.insert(square(-1, -1, 2));
+        let aside = tree.insert(square(10, 5, 2));
+
+        let hits = tree.raycast(Vec2::ZERO, at(1, 0), Fix::new(100));
+        assert_eq!(hits, vec![(around, Fix::ZERO), (near, Fix::new(5)), (twin, Fix::new(5)), (far, Fix::new(20))]);
+        assert!(near < twin);
+
+        // Boxes entered past max_t are left out, the fat boxes do not count
+        let hits = tree.raycast(Vec2::ZERO, at(1, 0), Fix::new(19));
+        assert_eq!(hits.iter().map(|hit| hit.0).collect::<Vec<_>>(), vec![around, near, twin]);
+        let hits = tree.raycast(at(-20, 1), at(1, 0), Fix::new(100));
+        assert_eq!(hits.iter().map(|hit| hit.0).collect::<Vec<_>>(), vec![behind, around, near, twin, far]);
+        assert!(tree.raycast(at(0, 8), at(1, 0), Fix::new(100)).is_empty());
+        assert_eq!(tree.raycast(at(0, 6), at(1, 0), Fix::new(100)), vec![(aside, Fix::new(10))]);
+    }
+
+    // The same calls build the same tree, so pairs come out the same and
+    // in handle order
+    #[test]
+    fn pairs_are_deterministic() {
+        let build = || {
+            let mut random = Random(11);
+            let mut tree = DynamicTree::new(Fix::HALF);
+            let mut handles = Vec::new();
+            for _ in 0..300 {
+                handles.push(tree.insert(square(random.below(100), random.below(100), 1 + random.below(10))));
+            }
+            for handle in handles.iter().step_by(3) {
+                tree.remove(*handle);
+            }
+            for handle in handles.iter().skip(1).step_by(3) {
+                let aabb = square(random.below(100), random.below(100), 1 + random.below(10));
+                let displacement = aabb.min - tree.aabb(*handle).unwrap().min;
+                tree.move_to(*handle, aabb, displacement);
+            }
+            tree
+        };
+        let (first, second) = (build(), build());
+        let pairs = first.pairs();
+        assert!(!pairs.is_empty());
+        assert_eq!(pairs, second.pairs());
+        assert!(pairs.windows(2).all(|window| window[0] < window[1]));
+        assert!(pairs.iter().all(|(a, b)| a < b));
+        for ((_, a), (_, b)) in first.proxies.iter().zip(second.proxies.iter()) {
+            assert_eq!(first.nodes[a.leaf].aabb, second.nodes[b.leaf].aabb);
+        }
+        assert_eq!(first.height(), second.height());
+    }
+
+    #[test]
+    fn stale_handles_are_unknown() {
+        let mut tree = DynamicTree::new(Fix::ONE);
+        let stale = tree.insert(square(0, 0, 1));
+        tree.remove(stale);
+        let fresh = tree.insert(square(5, 5, 1));
+        assert_eq!(fresh.index(), stale.index());
+        assert_eq!(tree.aabb(stale), None);
+        assert_eq!(tree.fat_aabb(stale), None);
+        assert!(!tree.move_to(stale, square(0, 0, 1), Vec2::ZERO));
+        assert_eq!(tree.remove(stale), None);
+        assert_eq!(tree.aabb(fresh), Some(square(5, 5, 1)));
+    }
+}
@@ -0,0 +1,444 @@
+use crate::dmath::fix::Fix;
+use crate::dmath::line::{orientation, Orientation};
+use crate::dmath::vec2::Vec2;
+
+use super::support::Support;
+
+// GJK and EPA on the Minkowski difference b - a, which contains the
+// origin exactly when the shapes overlap.
+//
+// GJK walks a simplex of up to three difference points towards the
+// origin. If the simplex ends up around the origin the shapes overlap and
+// EPA grows it into a polygon until the edge nearest the origin lies on
+// the boundary of the difference, giving the penetration. Otherwise the
+// point nearest the origin gives the distance, and blending the source
+// points the same way gives the closest points on each shape.
+
+const GJK_ITERATIONS: usize = 32;
+const EPA_ITERATIONS: usize = 64;
+
+// EPA stops once a new support point gains less than this, about a
+// millionth of a unit
+const EPA_TOLERANCE: Fix = Fix::from_bits(1 << 10);
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct ClosestPoints {
+    pub point_a: Vec2,
+    pub point_b: Vec2,
+    pub distance: Fix,
+}
+
+// Unit normal pointing from a to b and how far b has to move along it to
+// separate the shapes
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Penetration {
+    pub normal: Vec2,
+    pub depth: Fix,
+}
+
+impl Penetration {
+    pub fn vector(self) -> Vec2 {
+        self.normal * self.depth
+    }
+}
+
+// Overlapping or touching
+pub fn intersects<A: Support + ?Sized, B: Support + ?Sized>(a: &A, b: &B) -> bool {
+    matches!(gjk(a, b), Outcome::Overlapping(_))
+}
+
+// None when the shapes overlap or touch
+pub fn closest_points<A: Support + ?Sized, B: Support + ?Sized>(a: &A, b: &B) -> Option<ClosestPoints> {
+    let Outcome::Apart(closest) = gjk(a, b) else {
+        return None;
+    };
+    Some(ClosestPoints { point_a: closest.a, point_b: closest.b, distance: closest.point.length() })
+}
+
+// Zero when the shapes overlap or touch
+pub fn distance<A: Support + ?Sized, B: Support + ?Sized>(a: &A, b: &B) -> Fix {
+    closest_points(a, b).map_or(Fix::ZERO, |closest| closest.distance)
+}
+
+// None when the shapes are apart
+pub fn penetration<A: Support + ?Sized, B: Support + ?Sized>(a: &A, b: &B) -> Option<Penetration> {
+    match gjk(a, b) {
+        Outcome::Apart(_) => None,
+        Outcome::Overlapping(simplex) => Some(epa(a, b, simplex)),
+    }
+}
+
+// Point of the difference with the support points it came from, or a
+// blend of such points
+#[derive(Clone, Copy, PartialEq)]
+struct Vertex {
+    a: Vec2,
+    b: Vec2,
+    point: Vec2,
+}
+
+impl Vertex {
+    fn lerp(self, other: Vertex, t: Fix) -> Vertex {
+        Vertex { a: self.a.lerp(other.a, t), b: self.b.lerp(other.b, t), point: self.point.lerp(other.point, t) }
+    }
+}
+
+fn support<A: Support + ?Sized, B: Support + ?Sized>(a: &A, b: &B, direction: Vec2) -> Vertex {
+    let direction = direction.normalize();
+    let (a, b) = (a.support(-direction), b.support(direction));
+    Vertex { a, b, point: b - a }
+}
+
+// Dot product with 60 fractional bits
+fn dot_wide(u: Vec2, v: Vec2) -> i128 {
+    let bits = |value: Fix| value.to_bits() as i128;
+    (bits(u.x) * bits(v.x)).saturating_add(bits(u.y) * bits(v.y))
+}
+
+// numerator / denominator for 0 <= numerator <= denominator
+fn ratio(numerator: i128, denominator: i128) -> Fix {
+    let shift = (128 - denominator.leading_zeros()).saturating_sub(96);
+    Fix::from_bits((((numerator >> shift) << 30) / (denominator >> shift)) as i64)
+}
+
+#[derive(Clone, Copy)]
+struct Simplex {
+    vertices: [Vertex; 3],
+    count: usize,
+}
+
+impl Simplex {
+    fn new(vertex: Vertex) -> Simplex {
+        Simplex { vertices: [vertex; 3], count: 1 }
+    }
+
+    fn from_slice(vertices: &[Vertex]) -> Simplex {
+        let mut simplex = Simplex::new(vertices[0]);
+        simplex.vertices[..vertices.len()].copy_from_slice(vertices);
+        simplex.count = vertices.len();
+        simplex
+    }
+
+    fn as_slice(&self) -> &[Vertex] {
+        &self.vertices[..self.count]
+    }
+
+    fn contains(&self, point: Vec2) -> bool {
+        self.as_slice().iter().any(|vertex| vertex.point == point)
+    }
+
+    fn push(&mut self, vertex: Vertex) {
+        self.vertices[self.count] = vertex;
+        self.count += 1;
+    }
+
+    // Closest point to the origin, dropping the vertices it does not
+    // depend on. None when a triangle holds the origin.
+    fn reduce(&mut self) -> Option<Vertex> {
+        match *self.as_slice() {
+            [p] => Some(p),
+            [p, q] => {
+                let (closest, kept) = closest_on_segment(p, q);
+                *self = kept;
+                Some(closest)
+            }
+            [p, q, r] => {
+                let turn = orientation(p.point, q.point, r.point);
+                let inside = turn != Orientation::Collinear
+                    && [(p, q), (q, r), (r, p)].iter().all(|(from, to)| {
+                        orientation(from.point, to.point, Vec2::ZERO) != opposite(turn)
+                    });
+                if inside {
+                    return None;
+                }
+                let (closest, kept) = [(p, q), (q, r), (r, p)].iter()
+                    .map(|(from, to)| closest_on_segment(*from, *to))
+                    .min_by_key(|(closest, _)| dot_wide(closest.point, closest.point))
+                    .unwrap();
+                *self = kept;
+                Some(closest)
+            }
+            _ => unreachable!(),
+        }
+    }
+}
+
+fn opposite(turn: Orientation) -> Orientation {
+    match turn {
+        Orientation::Clockwise => Orientation::CounterClockwise,
+        Orientation::Collinear => Orientation::Collinear,
+        Orientation::CounterClockwise => Orientation::Clockwise,
+    }
+}
+
+fn closest_on_segment(p: Vertex, q: Vertex) -> (Vertex, Simplex) {
+    let edge = q.point - p.point;
+    let numerator = -dot_wide(p.point, edge);
+    let denominator = dot_wide(edge, edge);
+    if numerator <= 0 {
+        (p, Simplex::new(p))
+    } else if numerator >= denominator {
+        (q, Simplex::new(q))
+    } else {
+        (p.lerp(q, ratio(numerator, denominator)), Simplex::from_slice(&[p, q]))
+    }
+}
+
+enum Outcome {
+    // The difference point nearest the origin
+    Apart(Vertex),
+    // The simplex around the origin, which may be a point or a segment
+    // through it when the shapes touch
+    Overlapping(Simplex),
+}
+
+fn gjk<A: Support + ?Sized, B: Support + ?Sized>(a: &A, b: &B) -> Outcome {
+    let mut simplex = Simplex::new(support(a, b, Vec2::UNIT_X));
+    let mut closest = simplex.vertices[0];
+    for _ in 0..GJK_ITERATIONS {
+        closest = match simplex.reduce() {
+            Some(closest) if closest.point != Vec2::ZERO => closest,
+            _ => return Outcome::Overlapping(simplex),
+        };
+        let vertex = support(a, b, -closest.point);
+        // Stop once the new point barely gets closer than the current one
+        let length = dot_wide(closest.point, closest.point);
+        let progress = length - dot_wide(closest.point, vertex.point);
+        if progress <= length >> 24 || simplex.contains(vertex.point) {
+            break;
+        }
+        simplex.push(vertex);
+    }
+    Outcome::Apart(closest)
+}
+
+// Grows the simplex around the origin into a counterclockwise polygon,
+// then pushes out its nearest edge until the difference ends there
+fn epa<A: Support + ?Sized, B: Support + ?Sized>(a: &A, b: &B, simplex: Simplex) -> Penetration {
+    let mut polygon: Vec<Vertex> = simplex.as_slice().to_vec();
+
+    // A point or segment through the origin first needs some width
+    if polygon.len() == 1 {
+        for direction in [Vec2::UNIT_X, -Vec2::UNIT_X] {
+            let vertex = support(a, b, direction);
+            if vertex.point != polygon[0].point {
+                polygon.push(vertex);
+                break;
+            }
+        }
+        if polygon.len() == 1 {
+            return Penetration { normal: Vec2::UNIT_Y, depth: Fix::ZERO };
+        }
+    }
+    if polygon.len() == 2 {
+        let side = (polygon[1].point - polygon[0].point).perp();
+        let flat = |vertex: Vertex| orientation(polygon[0].point, polygon[1].point, vertex.point) == Orientation::Collinear;
+        match [side, -side].iter().map(|direction| support(a, b, *direction)).find(|vertex| !flat(*vertex)) {
+            Some(vertex) => polygon.push(vertex),
+            None => return Penetration { normal: side.normalize(), depth: Fix::ZERO },
+        }
+    }
+    if orientation(polygon[0].point, polygon[1].point, polygon[2].point) == Orientation::Clockwise {
+        polygon.swap(1, 2);
+    }
+
+    let mut nearest = Penetration { normal: Vec2::UNIT_Y, depth: Fix::ZERO };
+    for _ in 0..EPA_ITERATIONS {
+        let count = polygon.len();
+        let mut best: Option<(usize, Vec2, Fix)> = None;
+        for index in 0..count {
+            let (from, to) = (polygon[index].point, polygon[(index + 1) % count].point);
+            let edge = to - from;
+            let normal = Vec2::new(edge.y, -edge.x).normalize();
+            if normal == Vec2::ZERO {
+                continue;
+            }
+            let distance = normal.dot(from);
+            if best.is_none_or(|(_, _, nearest)| distance < nearest) {
+                best = Some((index, normal, distance));
+            }
+        }
+        let Some((index, normal, distance)) = best else {
+            break;
+        };
+        nearest = Penetration { normal: -normal, depth: distance.max(Fix::ZERO) };
+
+        let vertex = support(a, b, normal);
+        if normal.dot(vertex.point) - distance <= EPA_TOLERANCE || polygon.iter().any(|v| v.point == vertex.point) {
+            break;
+        }
+        polygon.insert(index + 1, vertex);
+    }
+    nearest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collision::narrowphase::collide;
+    use crate::collision::shape::{Capsule, Circle, ConvexPolygon, OrientedBox, Shape};
+    use crate::collision::support::{MinkowskiSum, Rounded};
+    use crate::dmath::rotation::Rotation;
+
+    fn at(x: f64, y: f64) -> Vec2 {
+        Vec2::new(Fix::from(x), Fix::from(y))
+    }
+
+    fn square(x: f64, y: f64, half: f64, angle: f64) -> OrientedBox {
+        OrientedBox::new(at(x, y), at(half, half), Rotation::from_angle(Fix::from(angle)))
+    }
+
+    fn assert_near(value: Fix, expected: f64) {
+        assert!((f64::from(value) - expected).abs() < 1e-5, "{} is not {}", value, expected);
+    }
+
+    fn assert_within(point: Vec2, x: f64, y: f64, tolerance: f64) {
+        assert!((point - at(x, y)).length() < Fix::from(tolerance), "{:?} is not ({}, {})", point, x, y);
+    }
+
+    fn assert_near_point(point: Vec2, x: f64, y: f64) {
+        assert_within(point, x, y, 1e-5);
+    }
+
+    // A shape only known through its support function
+    struct Diamond {
+        center: Vec2,
+        size: Fix,
+    }
+
+    impl Support for Diamond {
+        fn support(&self, direction: Vec2) -> Vec2 {
+            let offset = if Fix::abs(direction.x) >= Fix::abs(direction.y) {
+                Vec2::new(if direction.x >= Fix::ZERO { self.size } else { -self.size }, Fix::ZERO)
+            } else {
+                Vec2::new(Fix::ZERO, if direction.y >= Fix::ZERO { self.size } else { -self.size })
+            };
+            self.center + offset
+        }
+    }
+
+    #[test]
+    fn apart_boxes_give_closest_points() {
+        let a = square(0.0, 0.0, 1.0, 0.0);
+        let b = square(3.5, 0.5, 1.0, 0.0);
+        assert!(!intersects(&a, &b));
+        assert!(penetration(&a, &b).is_none());
+        let closest = closest_points(&a, &b).unwrap();
+        assert_near(closest.distance, 1.5);
+        assert_near(distance(&a, &b), 1.5);
+        assert_near(closest.point_a.x, 1.0);
+        assert_near(closest.point_b.x, 2.5);
+        assert_near(closest.point_a.y - closest.point_b.y, 0.0);
+
+        // Corner to corner picks out both corners, swapping swaps them
+        let c = square(3.0, 4.0, 1.0, 0.0);
+        let closest = closest_points(&a, &c).unwrap();
+        assert_near(closest.distance, (1.0f64 + 4.0).sqrt());
+        assert_near_point(closest.point_a, 1.0, 1.0);
+        assert_near_point(closest.point_b, 2.0, 3.0);
+        let swapped = closest_points(&c, &a).unwrap();
+        assert_near_point(swapped.point_a, 2.0, 3.0);
+        assert_near_point(swapped.point_b, 1.0, 1.0);
+    }
+
+    #[test]
+    fn curved_shapes_measure_along_the_center_line() {
+        let a = Circle::new(at(0.0, 0.0), Fix::ONE);
+        let b = Circle::new(at(3.0, 4.0), Fix::ONE);
+        let closest = closest_points(&a, &b).unwrap();
+        assert_near(closest.distance, 3.0);
+        // GJK stops on the distance, which settles long before the points
+        // stop sliding along the curve
+        assert_within(closest.point_a, 0.6, 0.8, 1e-3);
+        assert_within(closest.point_b, 2.4, 3.2, 1e-3);
+
+        let rod = Capsule::new(at(-2.0, 0.0), at(2.0, 0.0), Fix::HALF);
+        assert_near(distance(&rod, &b), 17f64.sqrt() - 1.5);
+        assert_near(distance(&rod, &Circle::new(at(4.0, 0.0), Fix::ONE)), 0.5);
+    }
+
+    #[test]
+    fn overlap_gives_the_shortest_way_out() {
+        let a = square(0.0, 0.0, 1.0, 0.0);
+        let b = square(1.75, 0.5, 1.0, 0.0);
+        assert!(intersects(&a, &b));
+        assert!(closest_points(&a, &b).is_none());
+        assert_eq!(distance(&a, &b), Fix::ZERO);
+        let out = penetration(&a, &b).unwrap();
+        assert_near_point(out.normal, 1.0, 0.0);
+        assert_near(out.depth, 0.25);
+        assert_near_point(out.vector(), 0.25, 0.0);
+        // Moving b out along the vector leaves it touching
+        let moved = square(2.0, 0.5, 1.0, 0.0);
+        assert_near(penetration(&a, &moved).unwrap().depth, 0.0);
+
+        let swapped = penetration(&b, &a).unwrap();
+        assert_near_point(swapped.normal, -1.0, 0.0);
+        assert_near(swapped.depth, 0.25);
+
+        // The same box on top of itself has to move a full width
+        assert_near(penetration(&a, &a).unwrap().depth, 2.0);
+    }
+
+    #[test]
+    fn touching_counts_as_overlap() {
+        let a = square(0.0, 0.0, 1.0, 0.0);
+        for b in [square(2.0, 0.5, 1.0, 0.0), square(2.0, 2.0, 1.0, 0.0)] {
+            assert!(intersects(&a, &b));
+            assert!(closest_points(&a, &b).is_none());
+            assert_eq!(distance(&a, &b), Fix::ZERO);
+            assert_near(penetration(&a, &b).unwrap().depth, 0.0);
+        }
+        // A point on the edge of a segment, where the simplex never gets
+        // any width
+        let segment = ConvexPolygon::new(&[at(-1.0, 0.0), at(1.0, 0.0)]);
+        assert!(intersects(&segment, &at(0.5, 0.0)));
+        assert!(!intersects(&segment, &at(0.5, 0.001)));
+    }
+
+    #[test]
+    fn rounded_and_summed_shapes() {
+        let rounded = Rounded::new(square(0.0, 0.0, 1.0, 0.0), Fix::HALF);
+        assert_near(distance(&rounded, &at(2.0, 0.0)), 0.5);
+        assert_near(distance(&rounded, &at(2.0, 2.0)), 2f64.sqrt() - 0.5);
+        let out = penetration(&rounded, &at(1.25, 0.0)).unwrap();
+        assert_near_point(out.normal, 1.0, 0.0);
+        assert_near(out.depth, 0.25);
+
+        // A box swept along a segment reaches over the segment's length
+        let swept = MinkowskiSum::new(square(0.0, 0.0, 1.0, 0.0), ConvexPolygon::new(&[at(0.0, 0.0), at(3.0, 0.0)]));
+        assert_near(distance(&swept, &at(5.0, 0.0)), 1.0);
+        assert!(intersects(&swept, &at(3.5, 0.5)));
+    }
+
+    #[test]
+    fn user_shapes_only_need_a_support_function() {
+        let diamond = Diamond { center: at(0.0, 0.0), size: Fix::ONE };
+        let circle = Circle::new(at(2.0, 2.0), Fix::ONE);
+        assert_near(distance(&diamond, &circle), 1.5 * 2f64.sqrt() - 1.0);
+        let out = penetration(&diamond, &at(0.25, 0.25)).unwrap();
+        assert_near_point(out.normal, 0.5f64.sqrt(), 0.5f64.sqrt());
+        assert_near(out.depth, 0.25 * 2f64.sqrt());
+    }
+
+    // EPA and the clipping narrowphase measure the same depth, up to the
+    // slop the narrowphase allows itself on curved shapes
+    #[test]
+    fn penetration_agrees_with_the_narrowphase() {
+        let shapes: Vec<Shape> = vec![
+            square(0.0, 0.0, 1.0, 0.0).into(),
+            square(1.5, 1.0, 1.0, 0.5).into(),
+            Circle::new(at(0.5, 1.5), Fix::ONE).into(),
+            Capsule::new(at(-1.0, 1.5), at(2.0, 1.0), Fix::HALF).into(),
+            ConvexPolygon::new(&[at(0.5, -0.5), at(2.5, 0.0), at(1.0, 1.5)]).into(),
+        ];
+        for a in shapes.iter() {
+            for b in shapes.iter().filter(|b| !std::ptr::eq(*b, a)) {
+                let manifold = collide(a, b).unwrap();
+                let out = penetration(a, b).unwrap();
+                let gap = (f64::from(manifold.depth) - f64::from(out.depth)).abs();
+                assert!(gap < 2e-3, "{:?} {:?}: {} against {}", a, b, manifold.depth, out.depth);
+            }
+        }
+    }
+}
@@ -0,0 +1,128 @@
+// Identifies an entry in a broadphase. Slots of removed entries are
+// reused, the most recently removed first, so the same sequence of calls
+// hands out the same handles on every peer. Each reuse bumps the slot's
+// generation, so a handle kept after its entry was removed finds nothing
+// instead of the entry that took its slot.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub struct Handle {
+    index: u32,
+    generation: u32,
+}
+
+impl Handle {
+    fn new(index: usize, generation: u32) -> Handle {
+        Handle { index: index as u32, generation }
+    }
+
+    pub fn index(self) -> usize {
+        self.index as usize
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Slot<T> {
+    generation: u32,
+    value: Option<T>,
+}
+
+// Slots for entries with a free list, so handles stay small and a removed
+// slot is the next one given out
+#[derive(Debug, Clone)]
+pub(super) struct Slots<T> {
+    entries: Vec<Slot<T>>,
+    free: Vec<usize>,
+}
+
+impl<T> Slots<T> {
+    pub(super) fn new() -> Slots<T> {
+        Slots { entries: Vec::new(), free: Vec::new() }
+    }
+
+    pub(super) fn insert(&mut self, value: T) -> Handle {
+        match self.free.pop() {
+            Some(index) => {
+                let slot = &mut self.entries[index];
+                slot.value = Some(value);
+                Handle::new(index, slot.generation)
+            }
+            None => {
+                self.entries.push(Slot { generation: 0, value: Some(value) });
+                Handle::new(self.entries.len() - 1, 0)
+            }
+        }
+    }
+
+    pub(super) fn remove(&mut self, handle: Handle) -> Option<T> {
+        let slot = self.entries.get_mut(handle.index())?;
+        if slot.generation != handle.generation {
+            return None;
+        }
+        let value = slot.value.take()?;
+        slot.generation = slot.generation.wrapping_add(1);
+        self.free.push(handle.index());
+        Some(value)
+    }
+
+    pub(super) fn get(&self, handle: Handle) -> Option<&T> {
+        let slot = self.entries.get(handle.index())?;
+        if slot.generation != handle.generation {
+            return None;
+        }
+        slot.value.as_ref()
+    }
+
+    pub(super) fn get_mut(&mut self, handle: Handle) -> Option<&mut T> {
+        let slot = self.entries.get_mut(handle.index())?;
+        if slot.generation != handle.generation {
+            return None;
+        }
+        slot.value.as_mut()
+    }
+
+    pub(super) fn len(&self) -> usize {
+        self.entries.len() - self.free.len()
+    }
+
+    // Live entries in handle order
+    pub(super) fn iter(&self) -> impl Iterator<Item = (Handle, &T)> {
+        self.entries.iter().enumerate().filter_map(|(index, slot)| {
+            slot.value.as_ref().map(|value| (Handle::new(index, slot.generation), value))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn removed_slots_are_reused_under_a_new_generation() {
+        let mut slots = Slots::new();
+        let first = slots.insert('a');
+        let second = slots.insert('b');
+        assert_eq!(slots.remove(first), Some('a'));
+        let third = slots.insert('c');
+        assert_eq!(third.index(), first.index());
+        assert_ne!(third, first);
+        assert_eq!(slots.len(), 2);
+        assert_eq!(slots.iter().collect::<Vec<_>>(), vec![(third, &'c'), (second, &'b')]);
+    }
+
+    #[test]
+    fn stale_handles_find_nothing() {
+        let mut slots = Slots::new();
+        let stale = slots.insert(1);
+        slots.remove(stale);
+        let fresh = slots.insert(2);
+        assert_eq!(slots.get(stale), None);
+        assert_eq!(slots.get_mut(stale), None);
+        assert_eq!(slots.remove(stale), None);
+        assert_eq!(slots.get(fresh), Some(&2));
+        // Removing twice does not put the slot on the free list twice
+        assert_eq!(slots.remove(fresh), Some(2));
+        assert_eq!(slots.remove(fresh), None);
+        assert_eq!(slots.len(), 0);
+        let handles = [slots.insert(3), slots.insert(4)];
+        assert_ne!(handles[0].index(), handles[1].index());
+    }
+}
@@ -0,0 +1,44 @@
+use crate::dmath::fix::Fix;
+use crate::dmath::vec2::Vec2;
+
+// Contact point halfway between the two surfaces, with how far they
+// overlap there
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Contact {
+    pub point: Vec2,
+    pub depth: Fix,
+}
+
+// Unit normal pointing from the first shape to the second, the deepest
+// penetration along it and one or two contacts
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Manifold {
+    pub normal: Vec2,
+    pub depth: Fix,
+    contacts: [Contact; 2],
+    count: usize,
+}
+
+impl Contact {
+    pub fn new(point: Vec2, depth: Fix) -> Contact {
+        Contact { point, depth }
+    }
+}
+
+impl Manifold {
+    pub(super) fn new(normal: Vec2, contacts: &[Contact]) -> Manifold {
+        let mut stored = [Contact::new(Vec2::ZERO, Fix::ZERO); 2];
+        stored[..contacts.len()].copy_from_slice(contacts);
+        let depth = contacts.iter().map(|contact| contact.depth).max().unwrap();
+        Manifold { normal, depth, contacts: stored, count: contacts.len() }
+    }
+
+    pub fn contacts(&self) -> &[Contact] {
+        &self.contacts[..self.count]
+    }
+
+    // The same contact seen from the second shape
+    pub fn flipped(self) -> Manifold {
+        Manifold { normal: -self.normal, ..self }
+    }
+}
@@ -0,0 +1,17 @@
+pub mod dynamic_tree;
+pub mod gjk;
+pub mod handle;
+pub mod manifold;
+pub mod narrowphase;
+pub mod shape;
+pub mod spatial_hash;
+pub mod support;
+
+pub use dynamic_tree::DynamicTree;
+pub use gjk::{ClosestPoints, Penetration};
+pub use handle::Handle;
+pub use manifold::{Contact, Manifold};
+pub use narrowphase::collide;
+pub use shape::{Capsule, Circle, ConvexPolygon, OrientedBox, Shape};
+pub use spatial_hash::SpatialHash;
+pub use support::{MinkowskiSum, Rounded, Support};
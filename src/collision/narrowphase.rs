@@ -0,0 +1,312 @@
+use crate::dmath::fix::Fix;
+use crate::dmath::line::Segment;
+use crate::dmath::vec2::Vec2;
+
+use super::manifold::{Contact, Manifold};
+use super::shape::Shape;
+
+// Contact generation between any two shapes through their cores and radii.
+//
+// The face normals of both cores are the separating axis candidates. When
+// the cores overlap, the face with the least penetration becomes the
+// reference face and the most opposed face of the other core is clipped
+// against its sides, giving up to two contacts. When they are apart, the
+// closest points of the cores decide between that and a single contact
+// along the line between them, as for circles or corners meeting.
+
+// Difference in distance treated as none, about a thousandth of a unit
+const LINEAR_SLOP: Fix = Fix::from_bits(1 << 20);
+
+// None when the shapes are apart. Touching shapes give zero depth.
+pub fn collide(a: &Shape, b: &Shape) -> Option<Manifold> {
+    a.with_core(|a_core, a_radius| {
+        b.with_core(|b_core, b_radius| collide_cores(a_core, a_radius, b_core, b_radius))
+    })
+}
+
+// Edge of a core from points[index] to the next point, with its outward
+// unit normal
+#[derive(Clone, Copy)]
+struct Face {
+    index: usize,
+    normal: Vec2,
+}
+
+// A segment core has both of its sides as faces, a point core has none
+fn faces(core: &[Vec2]) -> impl Iterator<Item = Face> + '_ {
+    let count = core.len();
+    (0..count).filter_map(move |index| {
+        let direction = core[(index + 1) % count] - core[index];
+        let normal = Vec2::new(direction.y, -direction.x).normalize();
+        if normal == Vec2::ZERO { None } else { Some(Face { index, normal }) }
+    })
+}
+
+// The face of core that separates it furthest from other, the first one
+// on ties
+fn best_face(core: &[Vec2], other: &[Vec2]) -> Option<(Fix, Face)> {
+    let mut best: Option<(Fix, Face)> = None;
+    for face in faces(core) {
+        let origin = core[face.index];
+        let separation = other.iter().map(|point| (*point - origin).dot(face.normal)).min().unwrap();
+        if best.is_none_or(|(largest, _)| separation > largest) {
+            best = Some((separation, face));
+        }
+    }
+    best
+}
+
+fn edges(core: &[Vec2]) -> impl Iterator<Item = Segment> + '_ {
+    let count = core.len();
+    (0..count).map(move |index| Segment::new(core[index], core[(index + 1) % count]))
+}
+
+// Closest points of the outlines of two cores, the first pair on ties
+fn closest_points(a: &[Vec2], b: &[Vec2]) -> (Vec2, Vec2) {
+    edges(a)
+        .flat_map(|edge_a| edges(b).map(move |edge_b| edge_a.closest_points(edge_b)))
+        .min_by_key(|(point_a, point_b)| (*point_b - *point_a).length_squared_wide())
+        .unwrap()
+}
+
+fn collide_cores(a: &[Vec2], a_radius: Fix, b: &[Vec2], b_radius: Fix) -> Option<Manifold> {
+    if a.is_empty() || b.is_empty() {
+        return None;
+    }
+    let radius = a_radius + b_radius;
+    let face_a = best_face(a, b);
+    let face_b = best_face(b, a);
+    let separation = face_a.into_iter().chain(face_b).map(|(separation, _)| separation).max();
+    if separation.is_some_and(|separation| separation > radius) {
+        return None;
+    }
+
+    if separation.is_none_or(|separation| separation >= Fix::ZERO) {
+        let (point_a, point_b) = closest_points(a, b);
+        let offset = point_b - point_a;
+        let reach = radius.to_bits().unsigned_abs() as u128;
+        if offset.length_squared_wide() > reach * reach {
+            return None;
+        }
+        let distance = offset.length();
+        // Faces only bound the distance from below, a clearly larger
+        // distance means corners or round parts meet
+        if separation.is_none_or(|separation| distance - separation > LINEAR_SLOP) {
+            return Some(point_contact(point_a, a_radius, point_b, b_radius));
+        }
+    }
+
+    // Prefer the first shape's face unless the second one separates
+    // clearly further, so nearly equal faces do not flicker
+    let flip = match (face_a, face_b) {
+        (Some((separation_a, _)), Some((separation_b, _))) => separation_b > separation_a + LINEAR_SLOP,
+        (None, _) => true,
+        (Some(_), None) => false,
+    };
+    match (face_a, face_b) {
+        (Some((_, face)), _) if !flip => clip(a, a_radius, face, b, b_radius),
+        (_, Some((_, face))) => clip(b, b_radius, face, a, a_radius).map(Manifold::flipped),
+        _ => None,
+    }
+}
+
+fn point_contact(point_a: Vec2, a_radius: Fix, point_b: Vec2, b_radius: Fix) -> Manifold {
+    let offset = point_b - point_a;
+    // Coincident centers have no direction to go by
+    let normal = if offset == Vec2::ZERO { Vec2::UNIT_Y } else { offset.normalize() };
+    let surface_a = point_a + normal * a_radius;
+    let surface_b = point_b - normal * b_radius;
+    let depth = a_radius + b_radius - offset.length();
+    Manifold::new(normal, &[Contact::new(surface_a.lerp(surface_b, Fix::HALF), depth)])
+}
+
+// Clips the incident edge of the other core to the sides of the reference
+// face and keeps the points within reach. The normal points from the
+// reference core to the incident one.
+fn clip(reference: &[Vec2], reference_radius: Fix, face: Face, incident: &[Vec2], incident_radius: Fix) -> Option<Manifold> {
+    let start = reference[face.index];
+    let end = reference[(face.index + 1) % reference.len()];
+    let normal = face.normal;
+    let tangent = (end - start).normalize();
+
+    let feature = match faces(incident).min_by_key(|other| other.normal.dot(normal)) {
+        Some(other) => [incident[other.index], incident[(other.index + 1) % incident.len()]],
+        None => [incident[0], incident[0]],
+    };
+
+    let mut points = feature;
+    for (origin, direction) in [(start, tangent), (end, -tangent)] {
+        let distances = points.map(|point| (point - origin).dot(direction));
+        if distances[0] < Fix::ZERO && distances[1] < Fix::ZERO {
+            // Entirely beside the face, fall back to the unclipped edge
+            points = feature;
+            break;
+        }
+        // A point exactly on the side is inside, the other one then moves
+        // onto it
+        for (inside, outside) in [(0, 1), (1, 0)] {
+            if distances[outside] < Fix::ZERO && distances[inside] >= Fix::ZERO {
+                let t = distances[inside] / (distances[inside] - distances[outside]);
+                points[outside] = points[inside].lerp(points[outside], t);
+            }
+        }
+    }
+
+    let radius = reference_radius + incident_radius;
+    let mut contacts = [Contact::new(Vec2::ZERO, Fix::ZERO); 2];
+    let mut count = 0;
+    for (index, point) in points.iter().enumerate() {
+        if index == 1 && points[1] == points[0] {
+            break;
+        }
+        let separation = (*point - start).dot(normal);
+        if separation <= radius {
+            let middle = *point + normal * ((reference_radius - separation - incident_radius) * Fix::HALF);
+            contacts[count] = Contact::new(middle, radius - separation);
+            count += 1;
+        }
+    }
+    if count == 0 { None } else { Some(Manifold::new(normal, &contacts[..count])) }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collision::shape::{Capsule, Circle, ConvexPolygon, OrientedBox};
+    use crate::dmath::rotation::Rotation;
+
+    fn at(x: f64, y: f64) -> Vec2 {
+        Vec2::new(Fix::from(x), Fix::from(y))
+    }
+
+    fn circle(x: f64, y: f64, radius: f64) -> Shape {
+        Circle::new(at(x, y), Fix::from(radius)).into()
+    }
+
+    fn capsule(start: (f64, f64), end: (f64, f64), radius: f64) -> Shape {
+        Capsule::new(at(start.0, start.1), at(end.0, end.1), Fix::from(radius)).into()
+    }
+
+    fn square(x: f64, y: f64, half: f64, angle: f64) -> Shape {
+        OrientedBox::new(at(x, y), at(half, half), Rotation::from_angle(Fix::from(angle))).into()
+    }
+
+    fn polygon(points: &[(f64, f64)]) -> Shape {
+        let points: Vec<Vec2> = points.iter().map(|&(x, y)| at(x, y)).collect();
+        ConvexPolygon::new(&points).into()
+    }
+
+    fn near(a: Vec2, b: Vec2) -> bool {
+        (a - b).length() < Fix::from(1e-6)
+    }
+
+    // Normal, depth and contacts in any order, then the same pair the other
+    // way around, which only turns the normal
+    fn assert_manifold(a: &Shape, b: &Shape, normal: Vec2, depth: f64, contacts: &[((f64, f64), f64)]) {
+        let manifold = collide(a, b).expect("shapes should collide");
+        assert!(near(manifold.normal, normal), "normal {:?}", manifold.normal);
+        assert!((f64::from(manifold.depth) - depth).abs() < 1e-6, "depth {}", manifold.depth);
+        assert_eq!(manifold.contacts().len(), contacts.len(), "{:?}", manifold.contacts());
+        for &((x, y), depth) in contacts {
+            let found = manifold.contacts().iter().any(|contact| {
+                near(contact.point, at(x, y)) && (f64::from(contact.depth) - depth).abs() < 1e-6
+            });
+            assert!(found, "no contact at ({x}, {y}) in {:?}", manifold.contacts());
+        }
+
+        let swapped = collide(b, a).expect("swapped shapes should collide");
+        assert!(near(swapped.normal, -normal), "swapped normal {:?}", swapped.normal);
+        assert!((f64::from(swapped.depth) - depth).abs() < 1e-6, "swapped depth {}", swapped.depth);
+    }
+
+    #[test]
+    fn circle_pairs() {
+        let unit = circle(0.0, 0.0, 1.0);
+        assert_manifold(&unit, &circle(1.5, 0.0, 1.0), at(1.0, 0.0), 0.5, &[((0.75, 0.0), 0.5)]);
+        assert_manifold(&unit, &circle(0.0, 2.0, 1.0), at(0.0, 1.0), 0.0, &[((0.0, 1.0), 0.0)]);
+        assert!(collide(&unit, &circle(2.001, 0.0, 1.0)).is_none());
+        // Coincident centers push along +y whichever shape comes first
+        let inner = circle(0.0, 0.0, 0.5);
+        for manifold in [collide(&unit, &inner).unwrap(), collide(&inner, &unit).unwrap()] {
+            assert_eq!(manifold.normal, Vec2::UNIT_Y);
+            assert_eq!(manifold.depth, Fix::from(1.5));
+        }
+
+        let rod = capsule((-2.0, 0.0), (2.0, 0.0), 0.5);
+        assert_manifold(&rod, &circle(1.0, 1.0, 0.75), at(0.0, 1.0), 0.25, &[((1.0, 0.375), 0.25)]);
+        assert_manifold(&rod, &circle(3.0, 0.0, 0.75), at(1.0, 0.0), 0.25, &[((2.375, 0.0), 0.25)]);
+        assert!(collide(&rod, &circle(0.0, -1.5, 0.75)).is_none());
+
+        let block = square(0.0, 0.0, 1.0, 0.0);
+        assert_manifold(&block, &circle(0.0, 1.5, 1.0), at(0.0, 1.0), 0.5, &[((0.0, 0.75), 0.5)]);
+        assert_manifold(&block, &circle(0.0, 0.5, 0.25), at(0.0, 1.0), 0.75, &[((0.0, 0.625), 0.75)]);
+        let half_root = 0.5f64.sqrt();
+        let depth = 1.0 - half_root;
+        let point = 1.0 - depth / 2.0 * half_root;
+        assert_manifold(&block, &circle(1.5, 1.5, 1.0), at(half_root, half_root), depth, &[((point, point), depth)]);
+
+        let triangle = polygon(&[(0.0, 0.0), (2.0, 0.0), (0.0, 2.0)]);
+        assert_manifold(&triangle, &circle(1.0, -0.5, 1.0), at(0.0, -1.0), 0.5, &[((1.0, 0.25), 0.5)]);
+        assert!(collide(&triangle, &circle(2.0, 2.0, 1.0)).is_none());
+    }
+
+    #[test]
+    fn capsule_pairs() {
+        let rod = capsule((-2.0, 0.0), (2.0, 0.0), 0.5);
+        let above = capsule((-1.0, 0.75), (3.0, 0.75), 0.5);
+        assert_manifold(&rod, &above, at(0.0, 1.0), 0.25, &[((2.0, 0.375), 0.25), ((-1.0, 0.375), 0.25)]);
+        // End caps meeting in line
+        assert_manifold(&rod, &capsule((3.0, 0.0), (5.0, 0.0), 0.5), at(1.0, 0.0), 0.0, &[((2.5, 0.0), 0.0)]);
+
+        let block = square(0.0, 0.0, 1.0, 0.0);
+        let lying = capsule((-3.0, 1.25), (0.5, 1.25), 0.5);
+        assert_manifold(&block, &lying, at(0.0, 1.0), 0.25, &[((-1.0, 0.875), 0.25), ((0.5, 0.875), 0.25)]);
+        let standing = capsule((0.0, 1.25), (0.0, 3.0), 0.5);
+        assert_manifold(&block, &standing, at(0.0, 1.0), 0.25, &[((0.0, 0.875), 0.25)]);
+
+        let triangle = polygon(&[(0.0, 0.0), (2.0, 0.0), (1.0, 2.0)]);
+        assert!(collide(&triangle, &capsule((-1.0, -1.0), (3.0, -1.0), 0.5)).is_none());
+        assert_manifold(&triangle, &capsule((-1.0, -0.25), (3.0, -0.25), 0.5), at(0.0, -1.0), 0.25,
+            &[((0.0, 0.125), 0.25), ((2.0, 0.125), 0.25)]);
+    }
+
+    #[test]
+    fn box_pairs() {
+        let block = square(0.0, 0.0, 1.0, 0.0);
+        // Edge on edge, clipped to the overlap of the two faces
+        assert_manifold(&block, &square(0.5, 1.75, 1.0, 0.0), at(0.0, 1.0), 0.25,
+            &[((1.0, 0.875), 0.25), ((-0.5, 0.875), 0.25)]);
+        assert_manifold(&block, &square(0.5, 2.0, 1.0, 0.0), at(0.0, 1.0), 0.0,
+            &[((1.0, 1.0), 0.0), ((-0.5, 1.0), 0.0)]);
+        assert!(collide(&block, &square(0.5, 2.001, 1.0, 0.0)).is_none());
+        // Corner into a face
+        let height = 1.0 + 2f64.sqrt() - 0.125;
+        let tilted = square(0.0, height, 1.0, std::f64::consts::FRAC_PI_4);
+        assert_manifold(&block, &tilted, at(0.0, 1.0), 0.125, &[((0.0, 0.9375), 0.125)]);
+        // Corners meeting diagonally, apart by the slop and more
+        assert!(collide(&block, &square(2.01, 2.01, 1.0, 0.0)).is_none());
+    }
+
+    #[test]
+    fn polygon_pairs() {
+        let hexagon = polygon(&[(1.0, 0.0), (0.5, 0.75), (-0.5, 0.75), (-1.0, 0.0), (-0.5, -0.75), (0.5, -0.75)]);
+        let lid = polygon(&[(-2.0, 0.5), (0.0, 0.5), (0.0, 1.5), (-2.0, 1.5)]);
+        assert_manifold(&hexagon, &lid, at(0.0, 1.0), 0.25, &[((0.0, 0.625), 0.25), ((-0.5, 0.625), 0.25)]);
+        let triangle = polygon(&[(0.0, 0.75), (1.0, 2.0), (-1.0, 2.0)]);
+        assert_manifold(&hexagon, &triangle, at(0.0, 1.0), 0.0, &[((0.0, 0.75), 0.0)]);
+        assert!(collide(&hexagon, &polygon(&[(3.0, 0.0), (4.0, 0.0), (3.5, 1.0)])).is_none());
+    }
+
+    // The incident edge ends exactly on the side of the reference face
+    // and reaches past it. The end on the side must stay the only contact.
+    #[test]
+    fn incident_edge_ending_on_a_side_is_clipped() {
+        let block = square(1.0, 1.0, 1.0, 0.0);
+        let plate = polygon(&[(-1.0, 1.875), (0.0, 1.875), (2.5, 1.9), (2.5, 3.0), (-1.0, 3.0)]);
+        let manifold = collide(&block, &plate).unwrap();
+        assert_eq!(manifold.normal, Vec2::UNIT_Y);
+        assert_eq!(manifold.contacts(), &[Contact::new(at(0.0, 1.9375), Fix::from(0.125))]);
+        assert!(collide(&plate, &block).unwrap().contacts().iter().all(|contact| contact.point.x >= Fix::ZERO));
+    }
+}
@@ -0,0 +1,147 @@
+use crate::dmath::aabb::Aabb;
+use crate::dmath::fix::Fix;
+use crate::dmath::polygon::Polygon;
+use crate::dmath::rotation::Rotation;
+use crate::dmath::vec2::Vec2;
+
+// Shapes in world space. Every shape is a convex core of one to n points
+// grown by a radius, which lets the narrowphase treat all pairs alike.
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Circle {
+    pub center: Vec2,
+    pub radius: Fix,
+}
+
+// Segment from start to end grown by the radius
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Capsule {
+    pub start: Vec2,
+    pub end: Vec2,
+    pub radius: Fix,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct OrientedBox {
+    pub center: Vec2,
+    pub half_extents: Vec2,
+    pub rotation: Rotation,
+}
+
+// Counterclockwise convex polygon without collinear points
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct ConvexPolygon {
+    points: Vec<Vec2>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub enum Shape {
+    Circle(Circle),
+    Capsule(Capsule),
+    Box(OrientedBox),
+    Polygon(ConvexPolygon),
+}
+
+impl Circle {
+    pub fn new(center: Vec2, radius: Fix) -> Circle {
+        Circle { center, radius }
+    }
+
+    pub fn aabb(self) -> Aabb {
+        Aabb::from_center_extents(self.center, Vec2::new(self.radius, self.radius))
+    }
+}
+
+impl Capsule {
+    pub fn new(start: Vec2, end: Vec2, radius: Fix) -> Capsule {
+        Capsule { start, end, radius }
+    }
+
+    pub fn aabb(self) -> Aabb {
+        Aabb::from_corners(self.start, self.end).expand(self.radius)
+    }
+}
+
+impl OrientedBox {
+    pub fn new(center: Vec2, half_extents: Vec2, rotation: Rotation) -> OrientedBox {
+        OrientedBox { center, half_extents, rotation }
+    }
+
+    // Counterclockwise from the corner at -x, -y in box space
+    pub fn corners(self) -> [Vec2; 4] {
+        let (x, y) = (self.half_extents.x, self.half_extents.y);
+        [Vec2::new(-x, -y), Vec2::new(x, -y), Vec2::new(x, y), Vec2::new(-x, y)]
+            .map(|corner| self.center + self.rotation.rotate(corner))
+    }
+
+    pub fn aabb(self) -> Aabb {
+        Aabb::from_points(&self.corners()).unwrap()
+    }
+}
+
+impl ConvexPolygon {
+    // Convex hull of the points, so any order and interior points are fine
+    pub fn new(points: &[Vec2]) -> ConvexPolygon {
+        ConvexPolygon { points: Polygon::convex_hull(points).points }
+    }
+
+    pub fn points(&self) -> &[Vec2] {
+        &self.points
+    }
+
+    // None for a polygon built from no points
+    pub fn aabb(&self) -> Option<Aabb> {
+        Aabb::from_points(&self.points)
+    }
+}
+
+impl From<&OrientedBox> for ConvexPolygon {
+    fn from(oriented_box: &OrientedBox) -> ConvexPolygon {
+        ConvexPolygon::new(&oriented_box.corners())
+    }
+}
+
+impl Shape {
+    pub fn aabb(&self) -> Option<Aabb> {
+        match self {
+            Shape::Circle(circle) => Some(circle.aabb()),
+            Shape::Capsule(capsule) => Some(capsule.aabb()),
+            Shape::Box(oriented_box) => Some(oriented_box.aabb()),
+            Shape::Polygon(polygon) => polygon.aabb(),
+        }
+    }
+
+    // Runs f on the core points and the radius, without allocating
+    pub(super) fn with_core<R>(&self, f: impl FnOnce(&[Vec2], Fix) -> R) -> R {
+        match self {
+            Shape::Circle(circle) => f(&[circle.center], circle.radius),
+            Shape::Capsule(capsule) => f(&[capsule.start, capsule.end], capsule.radius),
+            Shape::Box(oriented_box) => f(&oriented_box.corners(), Fix::ZERO),
+            Shape::Polygon(polygon) => f(&polygon.points, Fix::ZERO),
+        }
+    }
+}
+
+impl From<Circle> for Shape {
+    fn from(circle: Circle) -> Shape {
+        Shape::Circle(circle)
+    }
+}
+
+impl From<Capsule> for Shape {
+    fn from(capsule: Capsule) -> Shape {
+        Shape::Capsule(capsule)
+    }
+}
+
+impl From<OrientedBox> for Shape {
+    fn from(oriented_box: OrientedBox) -> Shape {
+        Shape::Box(oriented_box)
+    }
+}
+
+impl From<ConvexPolygon> for Shape {
+    fn from(polygon: ConvexPolygon) -> Shape {
+        Shape::Polygon(polygon)
+    }
+}
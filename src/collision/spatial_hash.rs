@@ -0,0 +1,288 @@
+use std::collections::HashMap;
+
+use crate::dmath::aabb::Aabb;
+use crate::dmath::fix::Fix;
+use crate::dmath::vec2::Vec2;
+
+use super::handle::{Handle, Slots};
+
+// Broadphase over a uniform grid of square cells. Each entry is listed in
+// every cell its box touches, so queries only look at the cells they
+// cover. Cells are keyed on floor(coordinate / cell size), and a box edge
+// on a cell boundary belongs to both cells.
+//
+// The cell map is a hash map, so nothing is ever returned in its order.
+// Results are sorted by handle, or by distance and then handle for rays,
+// which makes them the same on every peer. Large boxes touch many cells,
+// the cell size should be around the size of a typical entry.
+
+#[derive(Debug, Clone)]
+pub struct SpatialHash {
+    cell_size: Fix,
+    cells: HashMap<(i64, i64), Vec<Handle>>,
+    entries: Slots<Entry>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Entry {
+    aabb: Aabb,
+    cells: CellRange,
+}
+
+// Cells from min to max, both included
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+struct CellRange {
+    min: (i64, i64),
+    max: (i64, i64),
+}
+
+impl CellRange {
+    fn iter(self) -> impl Iterator<Item = (i64, i64)> {
+        (self.min.0..=self.max.0).flat_map(move |x| (self.min.1..=self.max.1).map(move |y| (x, y)))
+    }
+
+    fn contains(self, cell: (i64, i64)) -> bool {
+        self.min.0 <= cell.0 && cell.0 <= self.max.0 && self.min.1 <= cell.1 && cell.1 <= self.max.1
+    }
+}
+
+impl SpatialHash {
+    pub fn new(cell_size: Fix) -> SpatialHash {
+        assert!(cell_size > Fix::ZERO, "cell size must be positive");
+        SpatialHash { cell_size, cells: HashMap::new(), entries: Slots::new() }
+    }
+
+    pub fn cell_size(&self) -> Fix {
+        self.cell_size
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn insert(&mut self, aabb: Aabb) -> Handle {
+        let cells = self.cell_range(aabb);
+        let handle = self.entries.insert(Entry { aabb, cells });
+        self.add_to_cells(handle, cells);
+        handle
+    }
+
+    // The box the entry had, None for unknown handles
+    pub fn remove(&mut self, handle: Handle) -> Option<Aabb> {
+        let entry = self.entries.remove(handle)?;
+        self.remove_from_cells(handle, entry.cells);
+        Some(entry.aabb)
+    }
+
+    // Gives the entry a new box, only touching the cells when the box
+    // moves to other cells. False for unknown handles.
+    pub fn move_to(&mut self, handle: Handle, aabb: Aabb) -> bool {
+        let cells = self.cell_range(aabb);
+        let Some(entry) = self.entries.get_mut(handle) else {
+            return false;
+        };
+        let previous = entry.cells;
+        *entry = Entry { aabb, cells };
+        if previous != cells {
+            self.remove_from_cells(handle, previous);
+            self.add_to_cells(handle, cells);
+        }
+        true
+    }
+
+    pub fn aabb(&self, handle: Handle) -> Option<Aabb> {
+        self.entries.get(handle).map(|entry| entry.aabb)
+    }
+
+    // Entries whose boxes overlap or touch the box, sorted by handle
+    pub fn query_aabb(&self, aabb: Aabb) -> Vec<Handle> {
+        let mut found = self.candidates(self.cell_range(aabb));
+        found.retain(|handle| self.entries.get(*handle).unwrap().aabb.intersects(aabb));
+        found
+    }
+
+    // Entries whose boxes are within the radius of the center, sorted by
+    // handle
+    pub fn query_radius(&self, center: Vec2, radius: Fix) -> Vec<Handle> {
+        let reach = radius.to_bits().unsigned_abs() as u128;
+        let bounds = Aabb::from_center_extents(center, Vec2::new(radius, radius));
+        let mut found = self.candidates(self.cell_range(bounds));
+        found.retain(|handle| {
+            let aabb = self.entries.get(*handle).unwrap().aabb;
+            (aabb.closest_point(center) - center).length_squared_wide() <= reach * reach
+        });
+        found
+    }
+
+    // Entries hit by the ray origin + t * direction for t from zero to
+    // max_t, with the t where the ray enters each box. Sorted by t, then
+    // by handle.
+    pub fn raycast(&self, origin: Vec2, direction: Vec2, max_t: Fix) -> Vec<(Handle, Fix)> {
+        let mut hits = Vec::new();
+        // Only walk through the cells where there are entries
+        let Some(bounds) = self.entries.iter().map(|(_, entry)| entry.aabb).reduce(Aabb::union) else {
+            return hits;
+        };
+        let Some((enter, exit)) = bounds.ray_intersection(origin, direction) else {
+            return hits;
+        };
+        if enter > max_t {
+            return hits;
+        }
+        let limit = exit.min(max_t);
+        let occupied = self.cell_range(bounds);
+
+        let mut visited = Vec::new();
+        let start = origin + direction * enter;
+        let mut cell = self.cell_of(start);
+        cell = (cell.0.clamp(occupied.min.0, occupied.max.0), cell.1.clamp(occupied.min.1, occupied.max.1));
+
+        // Per axis: direction of the steps, t at the next cell boundary and
+        // t between boundaries
+        let axis = |origin: Fix, direction: Fix, cell: i64| -> (i64, Fix, Fix) {
+            if direction == Fix::ZERO {
+                return (0, Fix::MAX, Fix::MAX);
+            }
+            let step = if direction > Fix::ZERO { 1 } else { -1 };
+            let boundary = Fix::from(cell + (step + 1) / 2).saturating_mul(self.cell_size);
+            let next = (boundary - origin).saturating_div(direction);
+            let delta = self.cell_size.saturating_div(Fix::abs(direction));
+            (step, next, delta)
+        };
+        let (step_x, mut next_x, delta_x) = axis(origin.x, direction.x, cell.0);
+        let (step_y, mut next_y, delta_y) = axis(origin.y, direction.y, cell.1);
+
+        while occupied.contains(cell) {
+            if let Some(handles) = self.cells.get(&cell) {
+                visited.extend_from_slice(handles);
+            }
+            // Ties step along x first, the cell beyond the corner comes
+            // next either way
+            let along_x = step_x != 0 && (step_y == 0 || next_x <= next_y);
+            let next = if along_x { next_x } else { next_y };
+            if (step_x == 0 && step_y == 0) || next > limit {
+                break;
+            }
+            if along_x {
+                cell.0 += step_x;
+                next_x = next_x.saturating_add(delta_x);
+            } else {
+                cell.1 += step_y;
+                next_y = next_y.saturating_add(delta_y);
+            }
+        }
+
+        visited.sort();
+        visited.dedup();
+        for handle in visited {
+            let aabb = self.entries.get(handle).unwrap().aabb;
+            if let Some((enter, _)) = aabb.ray_intersection(origin, direction) {
+                if enter <= max_t {
+                    hits.push((handle, enter));
+                }
+            }
+        }
+        hits.sort_by_key(|(handle, t)| (*t, *handle));
+        hits
+    }
+
+    // Every pair of entries whose boxes overlap or touch, with the lower
+    // handle first, sorted
+    pub fn pairs(&self) -> Vec<(Handle, Handle)> {
+        let mut pairs = Vec::new();
+        for handles in self.cells.values() {
+            for (index, a) in handles.iter().enumerate() {
+                let aabb = self.entries.get(*a).unwrap().aabb;
+                for b in handles[index + 1..].iter() {
+                    if aabb.intersects(self.entries.get(*b).unwrap().aabb) {
+                        pairs.push(if a < b { (*a, *b) } else { (*b, *a) });
+                    }
+                }
+            }
+        }
+        pairs.sort();
+        pairs.dedup();
+        pairs
+    }
+
+    fn cell_of(&self, point: Vec2) -> (i64, i64) {
+        let cell = |value: Fix| i64::from(Fix::floor(value.saturating_div(self.cell_size)));
+        (cell(point.x), cell(point.y))
+    }
+
+    fn cell_range(&self, aabb: Aabb) -> CellRange {
+        CellRange { min: self.cell_of(aabb.min), max: self.cell_of(aabb.max) }
+    }
+
+    // Entries listed in the cells, sorted by handle without repeats
+    fn candidates(&self, cells: CellRange) -> Vec<Handle> {
+        let mut found = Vec::new();
+        let width = (cells.max.0 - cells.min.0 + 1).max(0) as u128;
+        let height = (cells.max.1 - cells.min.1 + 1).max(0) as u128;
+        if width * height > self.cells.len() as u128 {
+            // Fewer occupied cells than cells in the range
+            for (cell, handles) in self.cells.iter() {
+                if cells.contains(*cell) {
+                    found.extend_from_slice(handles);
+                }
+            }
+        } else {
+            for cell in cells.iter() {
+                if let Some(handles) = self.cells.get(&cell) {
+                    found.extend_from_slice(handles);
+                }
+            }
+        }
+        found.sort();
+        found.dedup();
+        found
+    }
+
+    fn add_to_cells(&mut self, handle: Handle, cells: CellRange) {
+        for cell in cells.iter() {
+            self.cells.entry(cell).or_default().push(handle);
+        }
+    }
+
+    fn remove_from_cells(&mut self, handle: Handle, cells: CellRange) {
+        for cell in cells.iter() {
+            if let Some(handles) = self.cells.get_mut(&cell) {
+                handles.retain(|other| *other != handle);
+                if handles.is_empty() {
+                    self.cells.remove(&cell);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square(x: i64, y: i64) -> Aabb {
+        Aabb::new(Vec2::new(Fix::new(x), Fix::new(y)), Vec2::new(Fix::new(x + 1), Fix::new(y + 1)))
+    }
+
+    // A handle kept after its entry was removed must not reach the entry
+    // that took over its slot
+    #[test]
+    fn stale_handles_are_unknown() {
+        let mut hash = SpatialHash::new(Fix::new(2));
+        let stale = hash.insert(square(0, 0));
+        assert_eq!(hash.remove(stale), Some(square(0, 0)));
+        let fresh = hash.insert(square(4, 4));
+        assert_eq!(fresh.index(), stale.index());
+
+        assert_eq!(hash.aabb(stale), None);
+        assert!(!hash.move_to(stale, square(8, 8)));
+        assert_eq!(hash.remove(stale), None);
+        assert_eq!(hash.aabb(fresh), Some(square(4, 4)));
+        assert_eq!(hash.query_aabb(square(4, 4)), vec![fresh]);
+        assert_eq!(hash.len(), 1);
+    }
+}
@@ -0,0 +1,120 @@
+use crate::dmath::fix::Fix;
+use crate::dmath::line::Segment;
+use crate::dmath::vec2::Vec2;
+
+use super::shape::{Capsule, Circle, ConvexPolygon, OrientedBox, Shape};
+
+// Convex shape described by its furthest point in a unit direction. This
+// is all GJK and EPA need, so any convex shape can implement it. Ties
+// should resolve the same way every time to keep results deterministic.
+pub trait Support {
+    fn support(&self, direction: Vec2) -> Vec2;
+}
+
+// Shape swept over another, for example a polygon over a circle
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct MinkowskiSum<A, B> {
+    pub a: A,
+    pub b: B,
+}
+
+// Shape grown by a radius in every direction
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Rounded<S> {
+    pub shape: S,
+    pub radius: Fix,
+}
+
+impl<A, B> MinkowskiSum<A, B> {
+    pub fn new(a: A, b: B) -> MinkowskiSum<A, B> {
+        MinkowskiSum { a, b }
+    }
+}
+
+impl<S> Rounded<S> {
+    pub fn new(shape: S, radius: Fix) -> Rounded<S> {
+        Rounded { shape, radius }
+    }
+}
+
+// The first of the points furthest along the direction, zero for no points
+fn furthest(points: &[Vec2], direction: Vec2) -> Vec2 {
+    let Some((first, rest)) = points.split_first() else {
+        return Vec2::ZERO;
+    };
+    let mut best = *first;
+    let mut best_distance = best.dot(direction);
+    for point in rest.iter() {
+        let distance = point.dot(direction);
+        if distance > best_distance {
+            best = *point;
+            best_distance = distance;
+        }
+    }
+    best
+}
+
+impl<A: Support, B: Support> Support for MinkowskiSum<A, B> {
+    fn support(&self, direction: Vec2) -> Vec2 {
+        self.a.support(direction) + self.b.support(direction)
+    }
+}
+
+impl<S: Support> Support for Rounded<S> {
+    fn support(&self, direction: Vec2) -> Vec2 {
+        self.shape.support(direction) + direction * self.radius
+    }
+}
+
+impl<S: Support + ?Sized> Support for &S {
+    fn support(&self, direction: Vec2) -> Vec2 {
+        (**self).support(direction)
+    }
+}
+
+impl Support for Vec2 {
+    fn support(&self, _direction: Vec2) -> Vec2 {
+        *self
+    }
+}
+
+impl Support for Segment {
+    fn support(&self, direction: Vec2) -> Vec2 {
+        furthest(&[self.start, self.end], direction)
+    }
+}
+
+impl Support for Circle {
+    fn support(&self, direction: Vec2) -> Vec2 {
+        self.center + direction * self.radius
+    }
+}
+
+impl Support for Capsule {
+    fn support(&self, direction: Vec2) -> Vec2 {
+        furthest(&[self.start, self.end], direction) + direction * self.radius
+    }
+}
+
+impl Support for OrientedBox {
+    fn support(&self, direction: Vec2) -> Vec2 {
+        furthest(&self.corners(), direction)
+    }
+}
+
+impl Support for ConvexPolygon {
+    fn support(&self, direction: Vec2) -> Vec2 {
+        furthest(self.points(), direction)
+    }
+}
+
+impl Support for Shape {
+    fn support(&self, direction: Vec2) -> Vec2 {
+        match self {
+            Shape::Circle(circle) => circle.support(direction),
+            Shape::Capsule(capsule) => capsule.support(direction),
+            Shape::Box(oriented_box) => oriented_box.support(direction),
+            Shape::Polygon(polygon) => polygon.support(direction),
+        }
+    }
+}
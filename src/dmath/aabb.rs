@@ -0,0 +1,139 @@
+use super::fix::Fix;
+use super::vec2::Vec2;
+
+// Axis-aligned box from min to max, edges included. Boxes with min above
+// max on either axis are empty.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Aabb {
+    pub min: Vec2,
+    pub max: Vec2,
+}
+
+impl Aabb {
+    pub fn new(min: Vec2, max: Vec2) -> Aabb {
+        Aabb { min, max }
+    }
+
+    // Box spanning two opposite corners given in any order
+    pub fn from_corners(a: Vec2, b: Vec2) -> Aabb {
+        Aabb { min: a.min(b), max: a.max(b) }
+    }
+
+    // Smallest box containing all the points, None for no points
+    pub fn from_points(points: &[Vec2]) -> Option<Aabb> {
+        let (first, rest) = points.split_first()?;
+        Some(rest.iter().fold(Aabb::new(*first, *first), |aabb, point| aabb.include_point(*point)))
+    }
+
+    pub fn from_center_extents(center: Vec2, half_extents: Vec2) -> Aabb {
+        Aabb { min: center - half_extents, max: center + half_extents }
+    }
+
+    pub fn center(self) -> Vec2 {
+        // Halving before adding keeps far away boxes from overflowing
+        let half = |min: Fix, max: Fix| min + (max - min) * Fix::HALF;
+        Vec2::new(half(self.min.x, self.max.x), half(self.min.y, self.max.y))
+    }
+
+    pub fn half_extents(self) -> Vec2 {
+        self.size() * Fix::HALF
+    }
+
+    pub fn size(self) -> Vec2 {
+        self.max - self.min
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.min.x > self.max.x || self.min.y > self.max.y
+    }
+
+    pub fn area(self) -> Fix {
+        let size = self.size();
+        size.x * size.y
+    }
+
+    pub fn perimeter(self) -> Fix {
+        let size = self.size();
+        (size.x + size.y) * Fix::TWO
+    }
+
+    pub fn union(self, other: Aabb) -> Aabb {
+        Aabb { min: self.min.min(other.min), max: self.max.max(other.max) }
+    }
+
+    // None when the boxes do not overlap. Boxes that only touch give a box
+    // with zero width or height.
+    pub fn intersection(self, other: Aabb) -> Option<Aabb> {
+        let result = Aabb { min: self.min.max(other.min), max: self.max.min(other.max) };
+        if result.is_empty() { None } else { Some(result) }
+    }
+
+    pub fn intersects(self, other: Aabb) -> bool {
+        self.min.x <= other.max.x && other.min.x <= self.max.x
+            && self.min.y <= other.max.y && other.min.y <= self.max.y
+    }
+
+    pub fn contains_point(self, point: Vec2) -> bool {
+        self.min.x <= point.x && point.x <= self.max.x
+            && self.min.y <= point.y && point.y <= self.max.y
+    }
+
+    pub fn contains(self, other: Aabb) -> bool {
+        self.min.x <= other.min.x && other.max.x <= self.max.x
+            && self.min.y <= other.min.y && other.max.y <= self.max.y
+    }
+
+    // Grown by the margin on every side, a negative margin shrinks
+    pub fn expand(self, margin: Fix) -> Aabb {
+        let margin = Vec2::new(margin, margin);
+        Aabb { min: self.min - margin, max: self.max + margin }
+    }
+
+    pub fn include_point(self, point: Vec2) -> Aabb {
+        Aabb { min: self.min.min(point), max: self.max.max(point) }
+    }
+
+    // The point itself when it is inside
+    pub fn closest_point(self, point: Vec2) -> Vec2 {
+        Vec2::new(point.x.clamp(self.min.x, self.max.x), point.y.clamp(self.min.y, self.max.y))
+    }
+
+    // Zero inside the box
+    pub fn distance(self, point: Vec2) -> Fix {
+        (point - self.closest_point(point)).length()
+    }
+
+    pub fn distance_squared(self, point: Vec2) -> Fix {
+        (point - self.closest_point(point)).length_squared()
+    }
+
+    // Slab test for the ray origin + t * direction with t >= 0. Returns
+    // the parameters where the ray enters and leaves the box, with the
+    // entry clamped to zero when the origin is inside.
+    pub fn ray_intersection(self, origin: Vec2, direction: Vec2) -> Option<(Fix, Fix)> {
+        let mut enter = Fix::ZERO;
+        let mut exit = Fix::MAX;
+        let axes = [
+            (origin.x, direction.x, self.min.x, self.max.x),
+            (origin.y, direction.y, self.min.y, self.max.y),
+        ];
+        for (origin, direction, min, max) in axes.iter().cloned() {
+            if direction == Fix::ZERO {
+                if origin < min || origin > max {
+                    return None;
+                }
+                continue;
+            }
+            // Saturating keeps nearly parallel rays from overflowing
+            let near = (min - origin).saturating_div(direction);
+            let far = (max - origin).saturating_div(direction);
+            let (near, far) = if near <= far { (near, far) } else { (far, near) };
+            enter = enter.max(near);
+            exit = exit.min(far);
+            if enter > exit {
+                return None;
+            }
+        }
+        Some((enter, exit))
+    }
+}
@@ -0,0 +1,184 @@
+use std::ops;
+
+use super::fix::Fix;
+use super::mat2::{InverseError, Mat2};
+use super::mat3::Mat3;
+use super::vec2::Vec2;
+use super::wide::I256;
+
+// Linear part followed by a translation: p' = matrix * p + translation
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Affine2 {
+    pub matrix: Mat2,
+    pub translation: Vec2,
+}
+
+// Decomposed form. The linear part is rotate * shear * scale, where the
+// shear adds shear * y to x. Mirroring shows up as a negative y scale.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Trs {
+    pub translation: Vec2,
+    pub angle: Fix,
+    pub scale: Vec2,
+    pub shear: Fix,
+}
+
+impl Affine2 {
+    pub const IDENTITY: Affine2 = Affine2 { matrix: Mat2::IDENTITY, translation: Vec2::ZERO };
+
+    pub fn new(matrix: Mat2, translation: Vec2) -> Affine2 {
+        Affine2 { matrix, translation }
+    }
+
+    pub fn from_translation(translation: Vec2) -> Affine2 {
+        Affine2::new(Mat2::IDENTITY, translation)
+    }
+
+    pub fn from_angle(angle: Fix) -> Affine2 {
+        Affine2::new(Mat2::from_angle(angle), Vec2::ZERO)
+    }
+
+    pub fn from_scale(scale: Vec2) -> Affine2 {
+        Affine2::new(Mat2::from_scale(scale), Vec2::ZERO)
+    }
+
+    pub fn from_shear(shear: Vec2) -> Affine2 {
+        Affine2::new(Mat2::from_shear(shear), Vec2::ZERO)
+    }
+
+    // Scale first, then rotate, then translate
+    pub fn from_translation_angle_scale(translation: Vec2, angle: Fix, scale: Vec2) -> Affine2 {
+        Affine2::from_trs(Trs { translation, angle, scale, shear: Fix::ZERO })
+    }
+
+    pub fn from_trs(trs: Trs) -> Affine2 {
+        let shear = Mat2::new(Fix::ONE, trs.shear, Fix::ZERO, Fix::ONE);
+        let matrix = Mat2::from_angle(trs.angle) * shear * Mat2::from_scale(trs.scale);
+        Affine2::new(matrix, trs.translation)
+    }
+
+    // Inverse of from_trs up to rounding. The angle is in (-PI, PI], and a
+    // zero x axis gives zero angle, scale and shear for the missing parts.
+    pub fn to_trs(self) -> Trs {
+        let x_axis = self.matrix.col(0);
+        let y_axis = self.matrix.col(1);
+        let angle = x_axis.angle();
+        let (sin, cos) = angle.sin_cos();
+        let scale_x = x_axis.length();
+        // The y axis rotated back into the frame of the x axis
+        let sheared = Vec2::new(y_axis.x * cos + y_axis.y * sin, y_axis.y * cos - y_axis.x * sin);
+        let shear = if sheared.y == Fix::ZERO { Fix::ZERO } else { sheared.x.saturating_div(sheared.y) };
+        Trs {
+            translation: self.translation,
+            angle,
+            scale: Vec2::new(scale_x, sheared.y),
+            shear,
+        }
+    }
+
+    pub fn determinant(self) -> Fix {
+        self.matrix.determinant()
+    }
+
+    pub fn transform_point(self, point: Vec2) -> Vec2 {
+        self.matrix * point + self.translation
+    }
+
+    // Ignores the translation
+    pub fn transform_vector(self, vector: Vec2) -> Vec2 {
+        self.matrix * vector
+    }
+
+    // Exact inverse rounded once per entry
+    pub fn inverse(self) -> Result<Affine2, InverseError> {
+        let matrix = self.matrix.inverse()?;
+        // -inverse * translation, as adjugate * translation / determinant
+        let [[a, b], [c, d]] = self.matrix.rows;
+        let wide = |value: Fix| I256::from_i128(value.to_bits() as i128);
+        let (x, y) = (wide(self.translation.x), wide(self.translation.y));
+        let determinant = self.matrix.determinant_wide();
+        let entry = |value: I256| value.shl(30).div_round(determinant).ok_or(InverseError::Overflow);
+        let translation_x = entry(wide(b) * y - wide(d) * x)?;
+        let translation_y = entry(wide(c) * x - wide(a) * y)?;
+        Ok(Affine2::new(matrix, Vec2::new(Fix::from_bits(translation_x), Fix::from_bits(translation_y))))
+    }
+
+    // Interpolates the decomposed parts, taking the shorter way around for
+    // the angle, so a rotation stays a rotation halfway through
+    pub fn lerp(self, other: Affine2, t: Fix) -> Affine2 {
+        let from = self.to_trs();
+        let to = other.to_trs();
+        let mut turn = to.angle - from.angle;
+        if turn > Fix::PI {
+            turn -= Fix::PI_TIMES_TWO;
+        } else if turn < -Fix::PI {
+            turn += Fix::PI_TIMES_TWO;
+        }
+        Affine2::from_trs(Trs {
+            translation: from.translation.lerp(to.translation, t),
+            angle: from.angle + turn * t,
+            scale: from.scale.lerp(to.scale, t),
+            shear: from.shear + (to.shear - from.shear) * t,
+        })
+    }
+
+    pub fn to_mat3(self) -> Mat3 {
+        let [[a, b], [c, d]] = self.matrix.rows;
+        Mat3::from_rows([
+            [a, b, self.translation.x],
+            [c, d, self.translation.y],
+            [Fix::ZERO, Fix::ZERO, Fix::ONE],
+        ])
+    }
+}
+
+impl From<Affine2> for Mat3 {
+    fn from(value: Affine2) -> Mat3 {
+        value.to_mat3()
+    }
+}
+
+// self * other applies other first
+impl ops::Mul for Affine2 {
+    type Output = Affine2;
+
+    fn mul(self, _rhs: Affine2) -> Affine2 {
+        Affine2::new(self.matrix * _rhs.matrix, self.transform_point(_rhs.translation))
+    }
+}
+
+impl ops::MulAssign for Affine2 {
+    fn mul_assign(&mut self, _rhs: Affine2) {
+        *self = *self * _rhs;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inverse_undoes_the_transform() {
+        let scale = Vec2::new(Fix::TWO, Fix::new(4));
+        let transform = Affine2::from_translation(Vec2::new(Fix::new(3), Fix::new(5))) * Affine2::from_scale(scale);
+        let inverse = transform.inverse().unwrap();
+        assert_eq!(inverse.matrix, Mat2::from_scale(Vec2::new(Fix::HALF, Fix::HALF * Fix::HALF)));
+        assert_eq!(inverse.translation, Vec2::new(Fix::new(-3) / Fix::TWO, Fix::new(-5) / Fix::new(4)));
+
+        let transform = Affine2::from_translation_angle_scale(Vec2::new(Fix::new(-7), Fix::HALF), Fix::new(2), scale);
+        let point = Vec2::new(Fix::new(11), Fix::new(-13));
+        let back = transform.inverse().unwrap().transform_point(transform.transform_point(point));
+        assert!(Fix::abs((back - point).x) < Fix::from_bits(64) && Fix::abs((back - point).y) < Fix::from_bits(64));
+    }
+
+    #[test]
+    fn inverse_tells_singular_from_overflow() {
+        let flat = Affine2::from_scale(Vec2::new(Fix::ONE, Fix::ZERO));
+        assert_eq!(flat.inverse(), Err(InverseError::Singular));
+        // The linear part inverts, moving back by 4 * 2^32 does not fit
+        let quarter = Mat2::from_scale(Vec2::new(Fix::HALF * Fix::HALF, Fix::ONE));
+        let far = Affine2::new(quarter, Vec2::new(Fix::new(1 << 32), Fix::ZERO));
+        assert_eq!(far.matrix.inverse().map(|_| ()), Ok(()));
+        assert_eq!(far.inverse(), Err(InverseError::Overflow));
+    }
+}
@@ -0,0 +1,125 @@
+use std::ops;
+
+use super::fix::Fix;
+
+// Binary angle: a full turn is 2^32, so sums and differences wrap around
+// the circle for free and the value is always exact. The bits split
+// directly into a quadrant and a position in the lookup tables.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Angle(u32);
+
+impl Angle {
+    pub const ZERO: Angle = Angle(0);
+    pub const QUARTER_TURN: Angle = Angle(1 << 30);
+    pub const HALF_TURN: Angle = Angle(1 << 31);
+    pub const THREE_QUARTER_TURN: Angle = Angle(3 << 30);
+
+    // Turns per radian and per degree, times 2^64
+    const TURNS_PER_RADIAN: i128 = 2935890503282001227;
+    const TURNS_PER_DEGREE: i128 = 51240955760304310;
+    const PI: i128 = 57952155664616982739;
+
+    pub const fn from_bits(bits: u32) -> Angle {
+        Angle(bits)
+    }
+
+    pub const fn to_bits(self) -> u32 {
+        self.0
+    }
+
+    // Rounds to the nearest binary angle, any number of turns is accepted
+    pub fn from_radians(radians: Fix) -> Angle {
+        Self::from_turns(radians.to_bits() as i128 * Self::TURNS_PER_RADIAN)
+    }
+
+    pub fn from_degrees(degrees: Fix) -> Angle {
+        Self::from_turns(degrees.to_bits() as i128 * Self::TURNS_PER_DEGREE)
+    }
+
+    // Turns with 30 + 64 fractional bits
+    fn from_turns(turns: i128) -> Angle {
+        Angle(((turns + (1 << 61)) >> 62) as u32)
+    }
+
+    // The angle is read as signed, so the result is in [-PI, PI)
+    pub fn to_radians(self) -> Fix {
+        let radians = self.0 as i32 as i128 * Self::PI;
+        Fix::from_bits(((radians + (1 << 64)) >> 65) as i64)
+    }
+
+    // In [-180, 180)
+    pub fn to_degrees(self) -> Fix {
+        Fix::from_bits((self.0 as i32 as i64 * 360 + 2) >> 2)
+    }
+
+    // Shortest arc from self to other, read as signed by to_radians and
+    // to_degrees. Exactly half a turn apart gives -PI.
+    pub fn difference(self, other: Angle) -> Angle {
+        other - self
+    }
+
+    // Interpolates along the shortest arc, t outside [0, 1] extrapolates
+    pub fn lerp(self, other: Angle, t: Fix) -> Angle {
+        let arc = self.difference(other).0 as i32 as i128;
+        let step = (arc * t.to_bits() as i128 + (1 << 29)) >> 30;
+        Angle(self.0.wrapping_add(step as u32))
+    }
+
+    pub fn sin(self) -> Fix {
+        Fix::sin_cos_binary(self.0).0
+    }
+
+    pub fn cos(self) -> Fix {
+        Fix::sin_cos_binary(self.0).1
+    }
+
+    pub fn sin_cos(self) -> (Fix, Fix) {
+        Fix::sin_cos_binary(self.0)
+    }
+
+    // Saturates at the quarter turns
+    pub fn tan(self) -> Fix {
+        Fix::tan_binary(self.0)
+    }
+}
+
+impl ops::Add<Angle> for Angle {
+    type Output = Angle;
+
+    #[inline(always)]
+    fn add(self, other: Angle) -> Angle {
+        Angle(self.0.wrapping_add(other.0))
+    }
+}
+
+impl ops::AddAssign<Angle> for Angle {
+    #[inline(always)]
+    fn add_assign(&mut self, other: Angle) {
+        *self = *self + other;
+    }
+}
+
+impl ops::Sub<Angle> for Angle {
+    type Output = Angle;
+
+    #[inline(always)]
+    fn sub(self, other: Angle) -> Angle {
+        Angle(self.0.wrapping_sub(other.0))
+    }
+}
+
+impl ops::SubAssign<Angle> for Angle {
+    #[inline(always)]
+    fn sub_assign(&mut self, other: Angle) {
+        *self = *self - other;
+    }
+}
+
+impl ops::Neg for Angle {
+    type Output = Angle;
+
+    #[inline(always)]
+    fn neg(self) -> Angle {
+        Angle(self.0.wrapping_neg())
+    }
+}
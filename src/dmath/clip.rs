@@ -0,0 +1,696 @@
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet};
+
+use super::angle::Angle;
+use super::fix::Fix;
+use super::line::{orientation, Orientation};
+use super::polygon::Polygon;
+use super::vec2::Vec2;
+use super::wide::I256;
+
+// Boolean operations and offsetting on sets of polygons.
+//
+// Every edge of both operands is split where it crosses or touches another
+// edge. Crossings are snap rounded: the exact crossing marks the pixel of
+// the grid point nearest to it, and every edge through a marked pixel, or
+// through the pixel of any end point, is bent through that grid point. The
+// bent edges no longer cross, so splitting ends once the touches left over
+// are split at their existing points. Each piece then learns the winding
+// numbers of both operands on its two sides from an exact ray cast, and
+// only pieces with the result inside on one side and outside on the other
+// are linked into rings. All side tests go through exact predicates, so
+// the same input gives the same output everywhere.
+//
+// Results are a flat list of rings. Rings winding counterclockwise go
+// around filled areas and rings winding clockwise around holes, with no
+// link from a hole to the ring around it: that is the smallest
+// counterclockwise ring containing it. Touching rings are kept apart, and
+// rings never cross each other or themselves. Offsetting expects its input
+// in the same form, and all operations take any mix of windings.
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum FillRule {
+    EvenOdd,
+    NonZero,
+    Positive,
+    Negative,
+}
+
+// How offsetting fills the gap at outward corners
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Join {
+    // Longest allowed miter as a multiple of the offset, longer ones are
+    // beveled
+    Miter(Fix),
+    // Largest distance allowed between the arc and its chords
+    Round(Fix),
+    Bevel,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Operation {
+    Union,
+    Intersection,
+    Difference,
+    Xor,
+}
+
+// Finest arc step for round joins, 4096 steps per turn
+const MIN_ARC_STEP: u32 = 1 << 20;
+
+// Piece of an operand edge from start to end, with start before end by x
+// then y, and the winding contribution to each operand along that direction
+#[derive(Clone, Copy)]
+struct Edge {
+    start: Vec2,
+    end: Vec2,
+    winding: [i32; 2],
+}
+
+fn key(point: Vec2) -> (Fix, Fix) {
+    (point.x, point.y)
+}
+
+fn bits(value: Fix) -> i128 {
+    value.to_bits() as i128
+}
+
+pub fn union(subject: &[Polygon], clip: &[Polygon], fill_rule: FillRule) -> Vec<Polygon> {
+    boolean(Operation::Union, subject, clip, fill_rule)
+}
+
+pub fn intersection(subject: &[Polygon], clip: &[Polygon], fill_rule: FillRule) -> Vec<Polygon> {
+    boolean(Operation::Intersection, subject, clip, fill_rule)
+}
+
+pub fn difference(subject: &[Polygon], clip: &[Polygon], fill_rule: FillRule) -> Vec<Polygon> {
+    boolean(Operation::Difference, subject, clip, fill_rule)
+}
+
+pub fn xor(subject: &[Polygon], clip: &[Polygon], fill_rule: FillRule) -> Vec<Polygon> {
+    boolean(Operation::Xor, subject, clip, fill_rule)
+}
+
+// Grows the polygons outwards by delta, or shrinks them for a negative
+// delta, merging whatever ends up overlapping
+pub fn offset(polygons: &[Polygon], delta: Fix, join: Join) -> Vec<Polygon> {
+    let arc_step = match join {
+        Join::Round(tolerance) => arc_step(Fix::abs(delta), tolerance),
+        _ => 0,
+    };
+    let rings: Vec<Polygon> = polygons.iter()
+        .map(|polygon| offset_ring(polygon, delta, join, arc_step))
+        .collect();
+    boolean(Operation::Union, &rings, &[], FillRule::Positive)
+}
+
+fn boolean(operation: Operation, subject: &[Polygon], clip: &[Polygon], fill_rule: FillRule) -> Vec<Polygon> {
+    let mut edges = Vec::new();
+    for (operand, polygons) in [subject, clip].iter().enumerate() {
+        for polygon in polygons.iter() {
+            let count = polygon.points.len();
+            for i in 0..count {
+                let mut winding = [0; 2];
+                winding[operand] = 1;
+                push_edge(&mut edges, polygon.points[i], polygon.points[(i + 1) % count], winding);
+            }
+        }
+    }
+    let edges = split_edges(merge_edges(edges));
+
+    let filled = |winding: i32| match fill_rule {
+        FillRule::EvenOdd => winding % 2 != 0,
+        FillRule::NonZero => winding != 0,
+        FillRule::Positive => winding > 0,
+        FillRule::Negative => winding < 0,
+    };
+    let inside = |winding: [i32; 2]| {
+        let (subject, clip) = (filled(winding[0]), filled(winding[1]));
+        match operation {
+            Operation::Union => subject || clip,
+            Operation::Intersection => subject && clip,
+            Operation::Difference => subject && !clip,
+            Operation::Xor => subject != clip,
+        }
+    };
+
+    let mut boundary = Vec::new();
+    for (index, edge) in edges.iter().enumerate() {
+        let beside = winding_beside(&edges, index);
+        let other = |sign: i32| [beside[0] + sign * edge.winding[0], beside[1] + sign * edge.winding[1]];
+        // The ray starts just to the +x side of the edge midpoint, which
+        // is the right side of edges going up
+        let (left, right) = if edge.end.y > edge.start.y { (other(1), beside) } else { (beside, other(-1)) };
+        match (inside(left), inside(right)) {
+            (true, false) => boundary.push((edge.start, edge.end)),
+            (false, true) => boundary.push((edge.end, edge.start)),
+            _ => {}
+        }
+    }
+    link_rings(boundary)
+}
+
+// Adds the edge in canonical direction, dropping zero length edges
+fn push_edge(edges: &mut Vec<Edge>, start: Vec2, end: Vec2, winding: [i32; 2]) {
+    match key(start).cmp(&key(end)) {
+        Ordering::Less => edges.push(Edge { start, end, winding }),
+        Ordering::Greater => edges.push(Edge { start: end, end: start, winding: [-winding[0], -winding[1]] }),
+        Ordering::Equal => {}
+    }
+}
+
+// Sorts the edges and sums the windings of identical ones. Edges that
+// cancel out do not change any winding number and are dropped.
+fn merge_edges(mut edges: Vec<Edge>) -> Vec<Edge> {
+    edges.sort_by_key(|edge| (key(edge.start), key(edge.end)));
+    let mut merged: Vec<Edge> = Vec::with_capacity(edges.len());
+    for edge in edges {
+        match merged.last_mut() {
+            Some(last) if last.start == edge.start && last.end == edge.end => {
+                last.winding[0] += edge.winding[0];
+                last.winding[1] += edge.winding[1];
+            }
+            _ => merged.push(edge),
+        }
+    }
+    merged.retain(|edge| edge.winding != [0, 0]);
+    merged
+}
+
+fn split_edges(mut edges: Vec<Edge>) -> Vec<Edge> {
+    loop {
+        let mut splits: Vec<Vec<Vec2>> = vec![Vec::new(); edges.len()];
+        let mut hot = BTreeSet::new();
+        // Sorted by start, so the x ranges can be swept
+        for i in 0..edges.len() {
+            for j in i + 1..edges.len() {
+                if edges[j].start.x > edges[i].end.x {
+                    break;
+                }
+                let (head, tail) = splits.split_at_mut(j);
+                find_splits(edges[i], edges[j], &mut head[i], &mut tail[0], &mut hot);
+            }
+        }
+        if hot.is_empty() && splits.iter().all(Vec::is_empty) {
+            return edges;
+        }
+
+        if !hot.is_empty() {
+            hot.extend(edges.iter().flat_map(|edge| [key(edge.start), key(edge.end)]));
+            for (edge, points) in edges.iter().zip(splits.iter_mut()) {
+                // Pixels overlapping the edge have their centers inside its
+                // x range
+                let candidates = hot.range((edge.start.x, Fix::MIN)..=(edge.end.x, Fix::MAX));
+                points.extend(candidates.map(|&(x, y)| Vec2::new(x, y)).filter(|&center| {
+                    center != edge.start && center != edge.end && passes_through(*edge, center)
+                }));
+            }
+        }
+
+        let mut pieces = Vec::with_capacity(edges.len() * 2);
+        for (edge, mut points) in edges.iter().zip(splits) {
+            // Points on the edge and the pixels it passes form a staircase
+            // leaving the start, so the Manhattan distance orders them
+            let start = edge.start;
+            points.sort_by_key(|point| {
+                ((bits(point.x) - bits(start.x)).abs() + (bits(point.y) - bits(start.y)).abs(), key(*point))
+            });
+            points.dedup();
+            let mut from = start;
+            for point in points.into_iter().chain(Some(edge.end)) {
+                push_edge(&mut pieces, from, point, edge.winding);
+                from = point;
+            }
+        }
+        edges = merge_edges(pieces);
+    }
+}
+
+// Reports where each edge has to be split at points it already has: where
+// the other edge ends on it. Proper crossings mark the pixel of the
+// crossing as hot instead.
+fn find_splits(a: Edge, b: Edge, splits_a: &mut Vec<Vec2>, splits_b: &mut Vec<Vec2>, hot: &mut BTreeSet<(Fix, Fix)>) {
+    let (a_low, a_high) = (a.start.y.min(a.end.y), a.start.y.max(a.end.y));
+    let (b_low, b_high) = (b.start.y.min(b.end.y), b.start.y.max(b.end.y));
+    if a_high < b_low || b_high < a_low {
+        return;
+    }
+
+    let sides_a = [orientation(a.start, a.end, b.start), orientation(a.start, a.end, b.end)];
+    let sides_b = [orientation(b.start, b.end, a.start), orientation(b.start, b.end, a.end)];
+
+    // For a point on the edge's line, strictly between the endpoints
+    let interior = |edge: Edge, point: Vec2| key(edge.start) < key(point) && key(point) < key(edge.end);
+    for (side, point) in sides_a.iter().zip([b.start, b.end].iter()) {
+        if *side == Orientation::Collinear && interior(a, *point) {
+            splits_a.push(*point);
+        }
+    }
+    for (side, point) in sides_b.iter().zip([a.start, a.end].iter()) {
+        if *side == Orientation::Collinear && interior(b, *point) {
+            splits_b.push(*point);
+        }
+    }
+
+    let crosses = |sides: [Orientation; 2]| {
+        sides[0] != Orientation::Collinear && sides[1] != Orientation::Collinear && sides[0] != sides[1]
+    };
+    if crosses(sides_a) && crosses(sides_b) {
+        hot.insert(key(crossing_pixel(a, b)));
+    }
+}
+
+// Center of the pixel holding the exact crossing of two edges that cross
+// properly, floor(crossing + 1/2) per coordinate
+fn crossing_pixel(a: Edge, b: Edge) -> Vec2 {
+    let wide = I256::from_i128;
+    let delta = |edge: Edge| (bits(edge.end.x) - bits(edge.start.x), bits(edge.end.y) - bits(edge.start.y));
+    let (da, db) = (delta(a), delta(b));
+    let offset = (bits(b.start.x) - bits(a.start.x), bits(b.start.y) - bits(a.start.y));
+    // The crossing is a.start + da * numerator / denominator
+    let denominator = wide(da.0) * wide(db.1) - wide(da.1) * wide(db.0);
+    let numerator = wide(offset.0) * wide(db.1) - wide(offset.1) * wide(db.0);
+    let two = wide(2);
+    let coordinate = |start: Fix, delta: i128| {
+        let doubled = two * (wide(bits(start)) * denominator + wide(delta) * numerator) + denominator;
+        // Inside both edges, so always in range
+        Fix::from_bits(doubled.div_floor(two * denominator).unwrap())
+    };
+    Vec2::new(coordinate(a.start.x, da.0), coordinate(a.start.y, da.1))
+}
+
+// Whether the edge passes through the pixel of a grid point, the square
+// from center - 1/2 bit up to but not including center + 1/2 bit on both
+// axes. Pixels tile the plane, so every point is in exactly one of them.
+fn passes_through(edge: Edge, center: Vec2) -> bool {
+    // In doubled coordinates the end points are even and the pixel sides
+    // odd, so an end point is never on a side
+    let double = |point: Vec2| [2 * bits(point.x), 2 * bits(point.y)];
+    let (start, end, center) = (double(edge.start), double(edge.end), double(center));
+    let (low, high) = ([center[0] - 1, center[1] - 1], [center[0] + 1, center[1] + 1]);
+    let spans = |axis: usize, value: i128| start[axis].min(end[axis]) < value && value < start[axis].max(end[axis]);
+    let apart = |axis: usize| start[axis].max(end[axis]) < low[axis] || high[axis] < start[axis].min(end[axis]);
+    if apart(0) || apart(1) {
+        return false;
+    }
+    let direction = (end[0] - start[0], end[1] - start[1]);
+    let side = |x: i128, y: i128| cross_sign(direction, (x - start[0], y - start[1]));
+    let sides = [side(low[0], low[1]), side(high[0], low[1]), side(high[0], high[1]), side(low[0], high[1])];
+    // Corners on both sides put the edge through the inside of the square.
+    // Otherwise it can only touch a corner, and the pixel owns the low one.
+    (sides.contains(&Ordering::Less) && sides.contains(&Ordering::Greater))
+        || (sides[0] == Ordering::Equal && (spans(0, low[0]) || spans(1, low[1])))
+}
+
+// Sign of the cross product u x v, exact
+fn cross_sign(u: (i128, i128), v: (i128, i128)) -> Ordering {
+    let small = |value: i128| value.abs() < 1 << 62;
+    if small(u.0) && small(u.1) && small(v.0) && small(v.1) {
+        return (u.0 * v.1).cmp(&(u.1 * v.0));
+    }
+    let cross = I256::from_i128(u.0) * I256::from_i128(v.1) - I256::from_i128(u.1) * I256::from_i128(v.0);
+    if cross.is_zero() {
+        Ordering::Equal
+    } else if cross.is_negative() {
+        Ordering::Less
+    } else {
+        Ordering::Greater
+    }
+}
+
+// Winding numbers of both operands at the midpoint of the edge, pushed
+// infinitesimally along +x and then +y, from a ray cast along +x against
+// every other edge. The midpoint is exact in doubled coordinates.
+fn winding_beside(edges: &[Edge], index: usize) -> [i32; 2] {
+    let edge = edges[index];
+    let (mx, my) = (bits(edge.start.x) + bits(edge.end.x), bits(edge.start.y) + bits(edge.end.y));
+    let mut winding = [0; 2];
+    for (other_index, other) in edges.iter().enumerate() {
+        if other_index == index || other.start.y == other.end.y {
+            continue;
+        }
+        let (low, high, sign) = if other.start.y < other.end.y {
+            (other.start, other.end, 1)
+        } else {
+            (other.end, other.start, -1)
+        };
+        // Half open in y, and only crossings right of the midpoint
+        if my < 2 * bits(low.y) || 2 * bits(high.y) <= my || 2 * bits(low.x).max(bits(high.x)) < mx {
+            continue;
+        }
+        let up = (bits(high.x) - bits(low.x), bits(high.y) - bits(low.y));
+        let to_mid = (mx - 2 * bits(low.x), my - 2 * bits(low.y));
+        if cross_sign(up, to_mid) == Ordering::Greater {
+            winding[0] += sign * other.winding[0];
+            winding[1] += sign * other.winding[1];
+        }
+    }
+    winding
+}
+
+// Orders candidate points by the clockwise angle from the direction back
+// towards from, as seen from at, so the first one is the sharpest left turn
+fn turn_order(at: Vec2, from: Vec2, a: Vec2, b: Vec2) -> Ordering {
+    // Collinear points are on the same side of at when they compare the
+    // same way with it
+    let same_direction = |point: Vec2| key(point).cmp(&key(at)) == key(from).cmp(&key(at));
+    // Clockwise side, straight on, counterclockwise side, straight back
+    let class = |point: Vec2| match orientation(at, from, point) {
+        Orientation::Clockwise => 0,
+        Orientation::Collinear if !same_direction(point) => 1,
+        Orientation::CounterClockwise => 2,
+        Orientation::Collinear => 3,
+    };
+    class(a).cmp(&class(b)).then_with(|| match orientation(at, a, b) {
+        Orientation::Clockwise => Ordering::Less,
+        Orientation::Collinear => Ordering::Equal,
+        Orientation::CounterClockwise => Ordering::Greater,
+    })
+}
+
+// Follows the directed boundary edges into rings, always taking the
+// sharpest left turn so rings that touch at a vertex stay separate
+fn link_rings(mut boundary: Vec<(Vec2, Vec2)>) -> Vec<Polygon> {
+    boundary.sort_by_key(|(start, end)| (key(*start), key(*end)));
+    let mut outgoing: BTreeMap<(Fix, Fix), Vec<usize>> = BTreeMap::new();
+    for (index, (start, _)) in boundary.iter().enumerate() {
+        outgoing.entry(key(*start)).or_default().push(index);
+    }
+
+    let mut used = vec![false; boundary.len()];
+    let mut rings = Vec::new();
+    for first in 0..boundary.len() {
+        if used[first] {
+            continue;
+        }
+        let mut points = Vec::new();
+        let mut current = first;
+        let closed = loop {
+            used[current] = true;
+            let (from, at) = boundary[current];
+            points.push(from);
+            let next = outgoing.get(&key(at)).and_then(|candidates| {
+                candidates.iter().cloned()
+                    .filter(|&candidate| !used[candidate] || candidate == first)
+                    .min_by(|&a, &b| turn_order(at, from, boundary[a].1, boundary[b].1))
+            });
+            match next {
+                Some(next) if next == first => break true,
+                Some(next) => current = next,
+                None => break false,
+            }
+        };
+        if closed {
+            remove_collinear(&mut points);
+            if points.len() >= 3 {
+                rings.push(Polygon::new(points));
+            }
+        }
+    }
+    rings
+}
+
+// Drops vertices on a straight line between their neighbours, including
+// spikes that double back
+fn remove_collinear(points: &mut Vec<Vec2>) {
+    loop {
+        let count = points.len();
+        if count < 3 {
+            return;
+        }
+        let flat = (0..count).find(|&k| {
+            orientation(points[(k + count - 1) % count], points[k], points[(k + 1) % count]) == Orientation::Collinear
+        });
+        match flat {
+            Some(k) => {
+                points.remove(k);
+            }
+            None => return,
+        }
+    }
+}
+
+// Largest power of two step, in binary angle bits, whose chords stay
+// within the tolerance of an arc with the given radius
+fn arc_step(radius: Fix, tolerance: Fix) -> u32 {
+    let mut step = Angle::QUARTER_TURN.to_bits();
+    while step > MIN_ARC_STEP && radius * (Fix::ONE - Angle::from_bits(step / 2).cos()) > tolerance {
+        step /= 2;
+    }
+    step
+}
+
+// Offsets every edge along its outward normal and joins the pieces at the
+// corners. Inward corners go back through the vertex, leaving small loops
+// that the final union removes.
+fn offset_ring(polygon: &Polygon, delta: Fix, join: Join, arc_step: u32) -> Polygon {
+    let mut points = polygon.points.clone();
+    points.dedup();
+    while points.len() > 1 && points.first() == points.last() {
+        points.pop();
+    }
+    let count = points.len();
+    if count < 2 {
+        return Polygon::new(Vec::new());
+    }
+
+    let normal = |from: Vec2, to: Vec2| {
+        let direction = to - from;
+        Vec2::new(direction.y, -direction.x).normalize()
+    };
+    let mut ring = Vec::with_capacity(count * 3);
+    for k in 0..count {
+        let (previous, vertex, next) = (points[(k + count - 1) % count], points[k], points[(k + 1) % count]);
+        let (before, after) = (normal(previous, vertex), normal(vertex, next));
+        let (first, last) = (vertex + before * delta, vertex + after * delta);
+
+        let turn = orientation(previous, vertex, next);
+        let outward = match turn {
+            Orientation::CounterClockwise => delta > Fix::ZERO,
+            Orientation::Clockwise => delta < Fix::ZERO,
+            Orientation::Collinear => before.dot(after) < Fix::ZERO,
+        };
+        if !outward {
+            if turn == Orientation::Collinear {
+                ring.push(first);
+            } else {
+                ring.extend([first, vertex, last].iter());
+            }
+            continue;
+        }
+
+        match join {
+            Join::Bevel => ring.extend([first, last].iter()),
+            Join::Miter(limit) => {
+                // The miter reaches delta / cos(half the turn) from the
+                // vertex, so it fits when 2 / (1 + cos) <= limit^2
+                let one_plus_cos = Fix::ONE + before.dot(after);
+                if one_plus_cos > Fix::ZERO && one_plus_cos * limit * limit >= Fix::TWO {
+                    ring.push(vertex + (before + after) * (delta / one_plus_cos));
+                } else {
+                    ring.extend([first, last].iter());
+                }
+            }
+            Join::Round(_) => {
+                let (start, end) = (before * delta, after * delta);
+                let counter_clockwise = delta > Fix::ZERO;
+                let (from, to) = (Angle::from_radians(start.angle()), Angle::from_radians(end.angle()));
+                let sweep = if counter_clockwise { (to - from).to_bits() } else { (from - to).to_bits() } as u64;
+                let steps = sweep.div_ceil(arc_step as u64).max(1);
+                ring.push(first);
+                for step in 1..steps {
+                    let angle = Angle::from_bits((sweep * step / steps) as u32);
+                    let (sin, cos) = angle.sin_cos();
+                    let sin = if counter_clockwise { sin } else { -sin };
+                    ring.push(vertex + Vec2::new(start.x * cos - start.y * sin, start.x * sin + start.y * cos));
+                }
+                ring.push(last);
+            }
+        }
+    }
+    Polygon::new(ring)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(x0: i64, y0: i64, x1: i64, y1: i64) -> Polygon {
+        let point = |x: i64, y: i64| Vec2::new(Fix::new(x), Fix::new(y));
+        Polygon::new(vec![point(x0, y0), point(x1, y0), point(x1, y1), point(x0, y1)])
+    }
+
+    fn area(rings: &[Polygon]) -> Fix {
+        rings.iter().fold(Fix::ZERO, |sum, ring| sum + ring.signed_area())
+    }
+
+    // No two edges of the result cross or overlap, and every ring is
+    // closed around some area
+    fn assert_clean(rings: &[Polygon]) {
+        let mut edges = Vec::new();
+        for ring in rings {
+            assert!(ring.points.len() >= 3 && ring.winding() != Orientation::Collinear);
+            let count = ring.points.len();
+            edges.extend((0..count).map(|i| (ring.points[i], ring.points[(i + 1) % count])));
+        }
+        for (i, &(a, b)) in edges.iter().enumerate() {
+            for &(c, d) in edges[i + 1..].iter() {
+                let sides = [orientation(a, b, c), orientation(a, b, d), orientation(c, d, a), orientation(c, d, b)];
+                let proper = sides.iter().all(|&side| side != Orientation::Collinear)
+                    && sides[0] != sides[1] && sides[2] != sides[3];
+                assert!(!proper, "{:?} crosses {:?}", (a, b), (c, d));
+                let span = |p: Vec2, q: Vec2| if key(p) < key(q) { (key(p), key(q)) } else { (key(q), key(p)) };
+                let ((low_1, high_1), (low_2, high_2)) = (span(a, b), span(c, d));
+                let collinear = sides.iter().all(|&side| side == Orientation::Collinear);
+                assert!(!collinear || low_1.max(low_2) >= high_1.min(high_2), "{:?} overlaps {:?}", (a, b), (c, d));
+            }
+        }
+    }
+
+    #[test]
+    fn overlapping_squares() {
+        let (a, b) = ([rect(0, 0, 2, 2)], [rect(1, 1, 3, 3)]);
+        let union = union(&a, &b, FillRule::NonZero);
+        assert_eq!((union.len(), union[0].points.len(), area(&union)), (1, 8, Fix::new(7)));
+        let overlap = intersection(&a, &b, FillRule::NonZero);
+        assert_eq!(overlap.len(), 1);
+        assert_eq!(Polygon::convex_hull(&overlap[0].points), Polygon::convex_hull(&rect(1, 1, 2, 2).points));
+        assert_eq!(area(&difference(&a, &b, FillRule::NonZero)), Fix::new(3));
+        assert_eq!(area(&xor(&a, &b, FillRule::NonZero)), Fix::new(6));
+        for rings in [union, overlap] {
+            assert_clean(&rings);
+            assert!(rings.iter().all(|ring| ring.winding() == Orientation::CounterClockwise));
+        }
+    }
+
+    #[test]
+    fn shared_edge_merges_and_touching_is_empty() {
+        let (a, b) = ([rect(0, 0, 1, 1)], [rect(1, 0, 2, 1)]);
+        let union = union(&a, &b, FillRule::NonZero);
+        assert_eq!(union.len(), 1);
+        assert_eq!(union[0].points.len(), 4);
+        assert_eq!(area(&union), Fix::TWO);
+        assert!(intersection(&a, &b, FillRule::NonZero).is_empty());
+        assert_eq!(area(&difference(&a, &b, FillRule::NonZero)), Fix::ONE);
+    }
+
+    #[test]
+    fn self_difference_is_empty() {
+        let mut shape = rect(0, 0, 3, 2);
+        shape.points.insert(1, Vec2::new(Fix::ONE, Fix::ZERO));
+        let a = [shape];
+        assert!(difference(&a, &a, FillRule::NonZero).is_empty());
+        assert!(xor(&a, &a, FillRule::EvenOdd).is_empty());
+        assert_eq!(area(&intersection(&a, &a, FillRule::NonZero)), Fix::new(6));
+        assert_eq!(area(&union(&a, &[], FillRule::NonZero)), Fix::new(6));
+    }
+
+    #[test]
+    fn holes_wind_clockwise() {
+        let cut = difference(&[rect(0, 0, 4, 4)], &[rect(1, 1, 3, 3)], FillRule::NonZero);
+        assert_eq!(cut.len(), 2);
+        assert_clean(&cut);
+        let (outer, hole): (Vec<&Polygon>, Vec<&Polygon>) =
+            cut.iter().partition(|ring| ring.winding() == Orientation::CounterClockwise);
+        assert_eq!((outer[0].signed_area(), hole[0].signed_area()), (Fix::new(16), Fix::new(-4)));
+        assert!(hole[0].points.iter().all(|&point| outer[0].contains_point(point)));
+
+        // The same shape as input, under both fill rules
+        let mut inner = rect(1, 1, 3, 3);
+        let nested = [rect(0, 0, 4, 4), inner.clone()];
+        assert_eq!(area(&union(&nested, &[], FillRule::EvenOdd)), Fix::new(12));
+        assert_eq!(area(&union(&nested, &[], FillRule::NonZero)), Fix::new(16));
+        inner.reverse();
+        assert_eq!(area(&union(&[rect(0, 0, 4, 4), inner], &[], FillRule::NonZero)), Fix::new(12));
+        // An island inside the hole stays a separate ring
+        let frame = difference(&[rect(0, 0, 6, 6)], &[rect(1, 1, 5, 5)], FillRule::NonZero);
+        let island = union(&frame, &[rect(2, 2, 4, 4)], FillRule::NonZero);
+        assert_eq!((island.len(), area(&island)), (3, Fix::new(24)));
+    }
+
+    #[test]
+    fn offset_joins() {
+        let square = [rect(0, 0, 2, 2)];
+        let miter = offset(&square, Fix::ONE, Join::Miter(Fix::TWO));
+        assert_eq!(Polygon::convex_hull(&miter[0].points), Polygon::convex_hull(&rect(-1, -1, 3, 3).points));
+        // A limit below sqrt(2) bevels right angles
+        let bevel = offset(&square, Fix::ONE, Join::Miter(Fix::ONE));
+        assert_eq!(area(&bevel), Fix::new(14));
+        assert_eq!(area(&offset(&square, Fix::ONE, Join::Bevel)), Fix::new(14));
+
+        let tolerance = Fix::ONE / Fix::new(1000);
+        let round = offset(&square, Fix::ONE, Join::Round(tolerance));
+        // Chords stay inside the arcs, at most the tolerance away over the
+        // full turn of corners
+        let expected = 12.0 + std::f64::consts::PI;
+        let error = f64::from(area(&round)) - expected;
+        assert!(error < 1e-6 && error > -std::f64::consts::TAU * f64::from(tolerance), "{}", error);
+        assert_clean(&round);
+
+        assert_eq!(area(&offset(&square, -Fix::HALF, Join::Bevel)), Fix::ONE);
+        assert!(offset(&square, -Fix::new(3) / Fix::TWO, Join::Miter(Fix::TWO)).is_empty());
+        // Holes shrink when the area around them grows
+        let framed = difference(&[rect(0, 0, 6, 6)], &[rect(2, 2, 4, 4)], FillRule::NonZero);
+        let grown = offset(&framed, Fix::HALF, Join::Miter(Fix::TWO));
+        assert_eq!(area(&grown), Fix::new(49) - Fix::ONE);
+    }
+
+    #[test]
+    fn pixels_own_their_low_corner() {
+        let bit = |x: i64, y: i64| Vec2::new(Fix::from_bits(x), Fix::from_bits(y));
+        let edge = |from: Vec2, to: Vec2| Edge { start: from, end: to, winding: [1, 0] };
+        // Through the corner shared by four pixels at (1/2, 1/2)
+        let diagonal = edge(bit(0, 1), bit(1, 0));
+        assert!(!passes_through(diagonal, bit(0, 0)));
+        assert!(passes_through(diagonal, bit(1, 1)));
+        assert!(passes_through(diagonal, bit(0, 1)) && passes_through(diagonal, bit(1, 0)));
+        // Reaching y = 1/2 at x = 2 steps up into the pixel above
+        let shallow = edge(bit(0, 0), bit(4, 1));
+        let passed: Vec<(i64, i64)> = (-1..6)
+            .flat_map(|x| (-1..3).map(move |y| (x, y)))
+            .filter(|&(x, y)| passes_through(shallow, bit(x, y)))
+            .collect();
+        assert_eq!(passed, [(0, 0), (1, 0), (2, 0), (2, 1), (3, 1), (4, 1)]);
+
+        // The crossing at (2, 1/2) rounds up into the pixel that owns it
+        let (a, b) = (edge(bit(0, 0), bit(4, 1)), edge(bit(0, 1), bit(4, 0)));
+        assert_eq!(crossing_pixel(a, b), bit(2, 1));
+        let (a, b) = (edge(bit(-4, -1), bit(0, 0)), edge(bit(-4, 0), bit(0, -1)));
+        assert_eq!(crossing_pixel(a, b), bit(-2, 0));
+    }
+
+    // Long edges a few bits apart in slope cross at points that round off
+    // the edges, and the rounded points used to create more crossings
+    // faster than a fixed number of splitting passes could resolve
+    #[test]
+    fn nearly_parallel_edges_end_without_crossings() {
+        let bit = |x: i64, y: i64| Vec2::new(Fix::from_bits(x), Fix::from_bits(y));
+        let mut seed = 0x3c6e_f372_fe94_f82b_u64;
+        let mut next = |range: i64| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            (seed % range as u64) as i64
+        };
+        for _ in 0..2 {
+            let mut slivers = Vec::new();
+            for _ in 0..30 {
+                let (y0, y1) = (next(64), next(64));
+                let far = (bit(1 << 40, y1), bit(1 << 40, y1 + 1 + next(5)));
+                slivers.push(Polygon::new(vec![bit(0, y0), far.0, far.1, bit(0, y0 + 1)]));
+            }
+            let (first, second) = slivers.split_at(15);
+            assert_clean(&xor(first, second, FillRule::EvenOdd));
+            assert_clean(&union(&slivers, &[], FillRule::EvenOdd));
+        }
+
+        let shapes: Vec<Polygon> = (0..12)
+            .map(|_| Polygon::new((0..5).map(|_| bit(next(1 << 12), next(1 << 12))).collect()))
+            .collect();
+        let (first, second) = shapes.split_at(6);
+        for fill_rule in [FillRule::EvenOdd, FillRule::NonZero] {
+            assert_clean(&union(first, second, fill_rule));
+            assert_clean(&intersection(first, second, fill_rule));
+        }
+    }
+}
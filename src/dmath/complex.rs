@@ -0,0 +1,332 @@
+use std::fmt;
+use std::ops;
+
+use super::fix::Fixed;
+use super::wide::I256;
+
+// Complex number over a fixed-point format, for example Complex<Fix>.
+// Products and quotients are computed exactly and rounded once per
+// component like the scalar operators, and the overflow families follow
+// Fix: the operators trap on overflow in debug builds and wrap in release.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Complex<T> {
+    pub re: T,
+    pub im: T,
+}
+
+impl<const FRAC: u32> Complex<Fixed<FRAC>> {
+    pub const ZERO: Self = Complex { re: Fixed::ZERO, im: Fixed::ZERO };
+    pub const ONE: Self = Complex { re: Fixed::ONE, im: Fixed::ZERO };
+    pub const I: Self = Complex { re: Fixed::ZERO, im: Fixed::ONE };
+
+    pub fn new(re: Fixed<FRAC>, im: Fixed<FRAC>) -> Self {
+        Complex { re, im }
+    }
+
+    pub fn from_polar(magnitude: Fixed<FRAC>, angle: Fixed<FRAC>) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        Complex { re: magnitude * cos, im: magnitude * sin }
+    }
+
+    pub fn to_polar(self) -> (Fixed<FRAC>, Fixed<FRAC>) {
+        (self.magnitude(), self.arg())
+    }
+
+    pub fn conj(self) -> Self {
+        Complex { re: self.re, im: -self.im }
+    }
+
+    // From the exact sum of squares, correctly rounded. Saturates to MAX
+    // when the magnitude does not fit.
+    pub fn magnitude(self) -> Fixed<FRAC> {
+        let re = self.re.to_bits().unsigned_abs() as u128;
+        let im = self.im.to_bits().unsigned_abs() as u128;
+        Fixed::sqrt_wide(re * re + im * im)
+    }
+
+    pub fn magnitude_squared(self) -> Fixed<FRAC> {
+        self.re * self.re + self.im * self.im
+    }
+
+    // Angle from the positive real axis, zero for zero
+    pub fn arg(self) -> Fixed<FRAC> {
+        Fixed::atan2(self.im, self.re)
+    }
+
+    // Repeated multiplication like Fix::powi. Overflow saturates each
+    // component, and zero to a negative power gives MAX.
+    pub fn powi(self, n: i32) -> Self {
+        let mut base = self;
+        let mut power = n.unsigned_abs();
+        let mut result = Self::ONE;
+        while power > 0 {
+            if power & 1 == 1 {
+                result = result.saturating_mul(base);
+            }
+            power >>= 1;
+            if power > 0 {
+                base = base.saturating_mul(base);
+            }
+        }
+        if n >= 0 {
+            result
+        } else if result == Self::ZERO {
+            Complex { re: Fixed::MAX, im: Fixed::ZERO }
+        } else {
+            Self::ONE.saturating_div(result)
+        }
+    }
+
+    fn narrow(wide: i128) -> (Fixed<FRAC>, bool) {
+        let narrow = wide as i64;
+        (Fixed::from_bits(narrow), narrow as i128 != wide)
+    }
+
+    fn saturate(wide: i128) -> Fixed<FRAC> {
+        Fixed::from_bits(wide.clamp(i64::MIN as i128, i64::MAX as i128) as i64)
+    }
+
+    // Product with FRAC fractional bits, truncated like the scalar product
+    fn mul_wide(self, rhs: Self) -> (i128, i128) {
+        let product = |a: Fixed<FRAC>, b: Fixed<FRAC>| a.to_bits() as i128 * b.to_bits() as i128;
+        // Only MIN * MIN terms can leave i128, and those overflow the
+        // result anyway
+        let re = product(self.re, rhs.re).saturating_sub(product(self.im, rhs.im));
+        let im = product(self.re, rhs.im).saturating_add(product(self.im, rhs.re));
+        (re >> FRAC, im >> FRAC)
+    }
+
+    // Quotient truncated towards zero like the scalar quotient, with the
+    // overflow flag and the sign of each component
+    fn div_wide(self, rhs: Self) -> [(i64, bool, bool); 2] {
+        let wide = |value: Fixed<FRAC>| I256::from_i128(value.to_bits() as i128);
+        let (a, b, c, d) = (wide(self.re), wide(self.im), wide(rhs.re), wide(rhs.im));
+        let divisor = c * c + d * d;
+        let component = |numerator: I256| {
+            let (value, overflow) = numerator.shl(FRAC).overflowing_div(divisor);
+            (value, overflow, numerator.is_negative())
+        };
+        [component(a * c + b * d), component(b * c - a * d)]
+    }
+
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        Some(Complex { re: self.re.checked_add(rhs.re)?, im: self.im.checked_add(rhs.im)? })
+    }
+
+    pub fn saturating_add(self, rhs: Self) -> Self {
+        Complex { re: self.re.saturating_add(rhs.re), im: self.im.saturating_add(rhs.im) }
+    }
+
+    pub fn wrapping_add(self, rhs: Self) -> Self {
+        Complex { re: self.re.wrapping_add(rhs.re), im: self.im.wrapping_add(rhs.im) }
+    }
+
+    pub fn overflowing_add(self, rhs: Self) -> (Self, bool) {
+        let (re, re_overflow) = self.re.overflowing_add(rhs.re);
+        let (im, im_overflow) = self.im.overflowing_add(rhs.im);
+        (Complex { re, im }, re_overflow || im_overflow)
+    }
+
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        Some(Complex { re: self.re.checked_sub(rhs.re)?, im: self.im.checked_sub(rhs.im)? })
+    }
+
+    pub fn saturating_sub(self, rhs: Self) -> Self {
+        Complex { re: self.re.saturating_sub(rhs.re), im: self.im.saturating_sub(rhs.im) }
+    }
+
+    pub fn wrapping_sub(self, rhs: Self) -> Self {
+        Complex { re: self.re.wrapping_sub(rhs.re), im: self.im.wrapping_sub(rhs.im) }
+    }
+
+    pub fn overflowing_sub(self, rhs: Self) -> (Self, bool) {
+        let (re, re_overflow) = self.re.overflowing_sub(rhs.re);
+        let (im, im_overflow) = self.im.overflowing_sub(rhs.im);
+        (Complex { re, im }, re_overflow || im_overflow)
+    }
+
+    pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+        match self.overflowing_mul(rhs) {
+            (result, false) => Some(result),
+            _ => None,
+        }
+    }
+
+    pub fn saturating_mul(self, rhs: Self) -> Self {
+        let (re, im) = self.mul_wide(rhs);
+        Complex { re: Self::saturate(re), im: Self::saturate(im) }
+    }
+
+    pub fn wrapping_mul(self, rhs: Self) -> Self {
+        self.overflowing_mul(rhs).0
+    }
+
+    pub fn overflowing_mul(self, rhs: Self) -> (Self, bool) {
+        let (re, im) = self.mul_wide(rhs);
+        let (re, re_overflow) = Self::narrow(re);
+        let (im, im_overflow) = Self::narrow(im);
+        (Complex { re, im }, re_overflow || im_overflow)
+    }
+
+    // Division by zero panics, except for checked_div which returns None
+    pub fn checked_div(self, rhs: Self) -> Option<Self> {
+        if rhs == Self::ZERO {
+            return None;
+        }
+        match self.overflowing_div(rhs) {
+            (result, false) => Some(result),
+            _ => None,
+        }
+    }
+
+    pub fn saturating_div(self, rhs: Self) -> Self {
+        let [re, im] = self.div_wide(rhs).map(|(value, overflow, negative)| match (overflow, negative) {
+            (false, _) => Fixed::from_bits(value),
+            (true, false) => Fixed::MAX,
+            (true, true) => Fixed::MIN,
+        });
+        Complex { re, im }
+    }
+
+    pub fn wrapping_div(self, rhs: Self) -> Self {
+        self.overflowing_div(rhs).0
+    }
+
+    pub fn overflowing_div(self, rhs: Self) -> (Self, bool) {
+        let [(re, re_overflow, _), (im, im_overflow, _)] = self.div_wide(rhs);
+        (Complex { re: Fixed::from_bits(re), im: Fixed::from_bits(im) }, re_overflow || im_overflow)
+    }
+
+    pub fn checked_neg(self) -> Option<Self> {
+        Some(Complex { re: self.re.checked_neg()?, im: self.im.checked_neg()? })
+    }
+
+    pub fn saturating_neg(self) -> Self {
+        Complex { re: self.re.saturating_neg(), im: self.im.saturating_neg() }
+    }
+
+    pub fn wrapping_neg(self) -> Self {
+        Complex { re: self.re.wrapping_neg(), im: self.im.wrapping_neg() }
+    }
+
+    pub fn overflowing_neg(self) -> (Self, bool) {
+        let (re, re_overflow) = self.re.overflowing_neg();
+        let (im, im_overflow) = self.im.overflowing_neg();
+        (Complex { re, im }, re_overflow || im_overflow)
+    }
+}
+
+impl<const FRAC: u32> From<Fixed<FRAC>> for Complex<Fixed<FRAC>> {
+    fn from(re: Fixed<FRAC>) -> Self {
+        Complex { re, im: Fixed::ZERO }
+    }
+}
+
+impl<const FRAC: u32> fmt::Display for Complex<Fixed<FRAC>> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{:+}i", self.re, self.im)
+    }
+}
+
+impl<const FRAC: u32> ops::Add for Complex<Fixed<FRAC>> {
+    type Output = Self;
+
+    #[inline(always)]
+    fn add(self, _rhs: Self) -> Self {
+        Complex { re: self.re + _rhs.re, im: self.im + _rhs.im }
+    }
+}
+
+impl<const FRAC: u32> ops::AddAssign for Complex<Fixed<FRAC>> {
+    #[inline(always)]
+    fn add_assign(&mut self, _rhs: Self) {
+        *self = *self + _rhs;
+    }
+}
+
+impl<const FRAC: u32> ops::Sub for Complex<Fixed<FRAC>> {
+    type Output = Self;
+
+    #[inline(always)]
+    fn sub(self, _rhs: Self) -> Self {
+        Complex { re: self.re - _rhs.re, im: self.im - _rhs.im }
+    }
+}
+
+impl<const FRAC: u32> ops::SubAssign for Complex<Fixed<FRAC>> {
+    #[inline(always)]
+    fn sub_assign(&mut self, _rhs: Self) {
+        *self = *self - _rhs;
+    }
+}
+
+impl<const FRAC: u32> ops::Neg for Complex<Fixed<FRAC>> {
+    type Output = Self;
+
+    #[inline(always)]
+    fn neg(self) -> Self {
+        Complex { re: -self.re, im: -self.im }
+    }
+}
+
+impl<const FRAC: u32> ops::Mul for Complex<Fixed<FRAC>> {
+    type Output = Self;
+
+    #[inline(always)]
+    fn mul(self, _rhs: Self) -> Self {
+        if cfg!(debug_assertions) {
+            self.checked_mul(_rhs).expect("attempt to multiply with overflow")
+        } else {
+            self.wrapping_mul(_rhs)
+        }
+    }
+}
+
+impl<const FRAC: u32> ops::MulAssign for Complex<Fixed<FRAC>> {
+    #[inline(always)]
+    fn mul_assign(&mut self, _rhs: Self) {
+        *self = *self * _rhs;
+    }
+}
+
+impl<const FRAC: u32> ops::Div for Complex<Fixed<FRAC>> {
+    type Output = Self;
+
+    #[inline(always)]
+    fn div(self, _rhs: Self) -> Self {
+        if cfg!(debug_assertions) {
+            let (result, overflow) = self.overflowing_div(_rhs);
+            if overflow {
+                panic!("attempt to divide with overflow");
+            }
+            result
+        } else {
+            self.wrapping_div(_rhs)
+        }
+    }
+}
+
+impl<const FRAC: u32> ops::DivAssign for Complex<Fixed<FRAC>> {
+    #[inline(always)]
+    fn div_assign(&mut self, _rhs: Self) {
+        *self = *self / _rhs;
+    }
+}
+
+impl<const FRAC: u32> ops::Mul<Fixed<FRAC>> for Complex<Fixed<FRAC>> {
+    type Output = Self;
+
+    #[inline(always)]
+    fn mul(self, _rhs: Fixed<FRAC>) -> Self {
+        Complex { re: self.re * _rhs, im: self.im * _rhs }
+    }
+}
+
+impl<const FRAC: u32> ops::Div<Fixed<FRAC>> for Complex<Fixed<FRAC>> {
+    type Output = Self;
+
+    #[inline(always)]
+    fn div(self, _rhs: Fixed<FRAC>) -> Self {
+        Complex { re: self.re / _rhs, im: self.im / _rhs }
+    }
+}
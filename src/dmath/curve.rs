@@ -0,0 +1,411 @@
+use super::fix::Fix;
+use super::line::Segment;
+use super::vec2::Vec2;
+
+// Parametric curves. Beziers run over t in [0, 1] and splines over
+// [0, segment count], one unit per segment. Everything past evaluation is
+// built on points and derivatives only, so it works the same for every
+// curve and gives the same answer on every machine.
+
+// Subdivision depth for flattening, 4096 pieces per unit of t at most
+const MAX_DEPTH: u32 = 12;
+
+const NEWTON_STEPS: usize = 4;
+
+pub trait Curve {
+    // The parameter runs from zero to this
+    fn end(&self) -> Fix;
+
+    fn point_at(&self, t: Fix) -> Vec2;
+
+    fn derivative_at(&self, t: Fix) -> Vec2;
+
+    fn second_derivative_at(&self, t: Fix) -> Vec2;
+
+    // Polyline from start to end staying within the tolerance of the curve
+    fn flatten(&self, tolerance: Fix) -> Vec<Vec2> {
+        flatten_parameters(self, tolerance).into_iter().map(|(_, point)| point).collect()
+    }
+
+    // Length of the flattened curve
+    fn length(&self, tolerance: Fix) -> Fix {
+        ArcLengthTable::new(self, tolerance).length()
+    }
+
+    fn arc_length_table(&self, tolerance: Fix) -> ArcLengthTable {
+        ArcLengthTable::new(self, tolerance)
+    }
+
+    // Best point of the flattened curve, polished with Newton steps on the
+    // curve itself
+    fn closest_parameter(&self, point: Vec2, tolerance: Fix) -> Fix {
+        let samples = flatten_parameters(self, tolerance);
+        let mut best = samples[0].0;
+        let mut best_distance = (samples[0].1 - point).length_squared_wide();
+        for pair in samples.windows(2) {
+            let ((t0, p0), (t1, p1)) = (pair[0], pair[1]);
+            let segment = Segment::new(p0, p1);
+            let distance = (segment.closest_point(point) - point).length_squared_wide();
+            if distance < best_distance {
+                best = t0 + (t1 - t0) * segment.closest_parameter(point);
+                best_distance = distance;
+            }
+        }
+
+        // Minimizes |P(t) - point|^2 by solving (P - point) . P' = 0
+        for _ in 0..NEWTON_STEPS {
+            let offset = self.point_at(best) - point;
+            let derivative = self.derivative_at(best);
+            let slope = derivative.dot(derivative) + offset.dot(self.second_derivative_at(best));
+            let step = match offset.dot(derivative).checked_div(slope) {
+                Some(step) if slope > Fix::ZERO => step,
+                _ => break,
+            };
+            let next = (best - step).clamp(Fix::ZERO, self.end());
+            let distance = (self.point_at(next) - point).length_squared_wide();
+            if distance >= best_distance {
+                break;
+            }
+            best = next;
+            best_distance = distance;
+        }
+        best
+    }
+
+    fn closest_point(&self, point: Vec2, tolerance: Fix) -> Vec2 {
+        self.point_at(self.closest_parameter(point, tolerance))
+    }
+}
+
+// Samples of the flattened curve with their parameters. Each unit of the
+// range is subdivided on its own until the quarter points are within the
+// tolerance of the chord.
+fn flatten_parameters<C: Curve + ?Sized>(curve: &C, tolerance: Fix) -> Vec<(Fix, Vec2)> {
+    let end = curve.end();
+    let mut samples = vec![(Fix::ZERO, curve.point_at(Fix::ZERO))];
+    let units = i64::from(Fix::ceiling(end));
+    for unit in 0..units {
+        let from = samples[samples.len() - 1];
+        let t = Fix::from(unit + 1).min(end);
+        subdivide(curve, tolerance, from, (t, curve.point_at(t)), 0, &mut samples);
+    }
+    samples
+}
+
+fn subdivide<C: Curve + ?Sized>(
+    curve: &C,
+    tolerance: Fix,
+    from: (Fix, Vec2),
+    to: (Fix, Vec2),
+    depth: u32,
+    samples: &mut Vec<(Fix, Vec2)>,
+) {
+    let span = to.0 - from.0;
+    let middle_t = from.0 + span * Fix::HALF;
+    let middle = (middle_t, curve.point_at(middle_t));
+    if depth < MAX_DEPTH {
+        let chord = Segment::new(from.1, to.1);
+        let quarter = span * Fix::HALF * Fix::HALF;
+        let flat = [middle.1, curve.point_at(from.0 + quarter), curve.point_at(to.0 - quarter)]
+            .iter()
+            .all(|point| chord.distance(*point) <= tolerance);
+        if !flat {
+            subdivide(curve, tolerance, from, middle, depth + 1, samples);
+            subdivide(curve, tolerance, middle, to, depth + 1, samples);
+            return;
+        }
+    }
+    samples.push(to);
+}
+
+// Cumulative chord lengths of a flattened curve, for moving along it at a
+// constant speed: point_at(table.parameter_at(distance))
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct ArcLengthTable {
+    parameters: Vec<Fix>,
+    distances: Vec<Fix>,
+}
+
+impl ArcLengthTable {
+    pub fn new<C: Curve + ?Sized>(curve: &C, tolerance: Fix) -> ArcLengthTable {
+        let samples = flatten_parameters(curve, tolerance);
+        let mut parameters = Vec::with_capacity(samples.len());
+        let mut distances = Vec::with_capacity(samples.len());
+        let mut distance = Fix::ZERO;
+        for (i, (t, point)) in samples.iter().enumerate() {
+            if i > 0 {
+                distance += (*point - samples[i - 1].1).length();
+            }
+            parameters.push(*t);
+            distances.push(distance);
+        }
+        ArcLengthTable { parameters, distances }
+    }
+
+    pub fn length(&self) -> Fix {
+        self.distances[self.distances.len() - 1]
+    }
+
+    // Parameter at the distance along the curve, clamped to the ends
+    pub fn parameter_at(&self, distance: Fix) -> Fix {
+        Self::interpolate(&self.distances, &self.parameters, distance)
+    }
+
+    // Distance along the curve at the parameter, clamped to the ends
+    pub fn distance_at(&self, t: Fix) -> Fix {
+        Self::interpolate(&self.parameters, &self.distances, t)
+    }
+
+    // Linear interpolation in a table with increasing keys
+    fn interpolate(keys: &[Fix], values: &[Fix], key: Fix) -> Fix {
+        let above = keys.partition_point(|k| *k <= key);
+        if above == 0 {
+            return values[0];
+        }
+        if above == keys.len() {
+            return values[values.len() - 1];
+        }
+        let (k0, k1) = (keys[above - 1], keys[above]);
+        let (v0, v1) = (values[above - 1], values[above]);
+        v0 + (v1 - v0) * ((key - k0) / (k1 - k0))
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct QuadraticBezier {
+    pub p0: Vec2,
+    pub p1: Vec2,
+    pub p2: Vec2,
+}
+
+impl QuadraticBezier {
+    pub fn new(p0: Vec2, p1: Vec2, p2: Vec2) -> QuadraticBezier {
+        QuadraticBezier { p0, p1, p2 }
+    }
+
+    // De Casteljau split into the parts before and after t
+    pub fn split(self, t: Fix) -> (QuadraticBezier, QuadraticBezier) {
+        let a = self.p0.lerp(self.p1, t);
+        let b = self.p1.lerp(self.p2, t);
+        let middle = a.lerp(b, t);
+        (QuadraticBezier::new(self.p0, a, middle), QuadraticBezier::new(middle, b, self.p2))
+    }
+
+    // The same curve as a cubic, exact up to rounding the thirds
+    pub fn to_cubic(self) -> CubicBezier {
+        let two_thirds = |from: Vec2| from + (self.p1 - from) * Fix::TWO / Fix::from(3);
+        CubicBezier::new(self.p0, two_thirds(self.p0), two_thirds(self.p2), self.p2)
+    }
+}
+
+impl Curve for QuadraticBezier {
+    fn end(&self) -> Fix {
+        Fix::ONE
+    }
+
+    fn point_at(&self, t: Fix) -> Vec2 {
+        self.p0.lerp(self.p1, t).lerp(self.p1.lerp(self.p2, t), t)
+    }
+
+    fn derivative_at(&self, t: Fix) -> Vec2 {
+        (self.p1 - self.p0).lerp(self.p2 - self.p1, t) * Fix::TWO
+    }
+
+    fn second_derivative_at(&self, _t: Fix) -> Vec2 {
+        (self.p2 - self.p1 * Fix::TWO + self.p0) * Fix::TWO
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct CubicBezier {
+    pub p0: Vec2,
+    pub p1: Vec2,
+    pub p2: Vec2,
+    pub p3: Vec2,
+}
+
+impl CubicBezier {
+    pub fn new(p0: Vec2, p1: Vec2, p2: Vec2, p3: Vec2) -> CubicBezier {
+        CubicBezier { p0, p1, p2, p3 }
+    }
+
+    // De Casteljau split into the parts before and after t
+    pub fn split(self, t: Fix) -> (CubicBezier, CubicBezier) {
+        let (a, b, c) = (self.p0.lerp(self.p1, t), self.p1.lerp(self.p2, t), self.p2.lerp(self.p3, t));
+        let (d, e) = (a.lerp(b, t), b.lerp(c, t));
+        let middle = d.lerp(e, t);
+        (CubicBezier::new(self.p0, a, d, middle), CubicBezier::new(middle, e, c, self.p3))
+    }
+}
+
+impl Curve for CubicBezier {
+    fn end(&self) -> Fix {
+        Fix::ONE
+    }
+
+    fn point_at(&self, t: Fix) -> Vec2 {
+        QuadraticBezier::new(self.p0.lerp(self.p1, t), self.p1.lerp(self.p2, t), self.p2.lerp(self.p3, t))
+            .point_at(t)
+    }
+
+    fn derivative_at(&self, t: Fix) -> Vec2 {
+        QuadraticBezier::new(self.p1 - self.p0, self.p2 - self.p1, self.p3 - self.p2).point_at(t) * Fix::from(3)
+    }
+
+    fn second_derivative_at(&self, t: Fix) -> Vec2 {
+        let first = self.p2 - self.p1 * Fix::TWO + self.p0;
+        let second = self.p3 - self.p2 * Fix::TWO + self.p1;
+        first.lerp(second, t) * Fix::from(6)
+    }
+}
+
+// Segments of a spline through or around its points. Closed splines wrap
+// around, open ones get mirrored points past their ends so they start and
+// stop exactly on the first and last point.
+fn segment_count(points: &[Vec2], closed: bool) -> usize {
+    match points.len() {
+        0 | 1 => 0,
+        count if closed => count,
+        count => count - 1,
+    }
+}
+
+fn neighbourhood(points: &[Vec2], closed: bool, index: usize) -> [Vec2; 4] {
+    let count = points.len() as isize;
+    let point = |i: isize| {
+        if closed {
+            points[i.rem_euclid(count) as usize]
+        } else if i < 0 {
+            points[0] * Fix::TWO - points[1]
+        } else if i >= count {
+            points[count as usize - 1] * Fix::TWO - points[count as usize - 2]
+        } else {
+            points[i as usize]
+        }
+    };
+    let index = index as isize;
+    [point(index - 1), point(index), point(index + 1), point(index + 2)]
+}
+
+// Segment index and parameter within it, clamped to the spline
+fn locate(t: Fix, segments: usize) -> (usize, Fix) {
+    let t = t.clamp(Fix::ZERO, Fix::from(segments as i64));
+    let index = (i64::from(Fix::floor(t)) as usize).min(segments - 1);
+    (index, t - Fix::from(index as i64))
+}
+
+// Uniform Catmull-Rom spline passing through every point
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct CatmullRom {
+    pub points: Vec<Vec2>,
+    pub closed: bool,
+}
+
+impl CatmullRom {
+    pub fn new(points: Vec<Vec2>, closed: bool) -> CatmullRom {
+        CatmullRom { points, closed }
+    }
+
+    pub fn segment_count(&self) -> usize {
+        segment_count(&self.points, self.closed)
+    }
+
+    // Segment from point index to the next one as a cubic Bezier
+    pub fn segment(&self, index: usize) -> CubicBezier {
+        let [p0, p1, p2, p3] = neighbourhood(&self.points, self.closed, index);
+        let sixth = Fix::from(6);
+        CubicBezier::new(p1, p1 + (p2 - p0) / sixth, p2 - (p3 - p1) / sixth, p2)
+    }
+
+    fn segment_at(&self, t: Fix) -> Option<(CubicBezier, Fix)> {
+        match self.segment_count() {
+            0 => None,
+            segments => {
+                let (index, local) = locate(t, segments);
+                Some((self.segment(index), local))
+            }
+        }
+    }
+}
+
+impl Curve for CatmullRom {
+    fn end(&self) -> Fix {
+        Fix::from(self.segment_count() as i64)
+    }
+
+    fn point_at(&self, t: Fix) -> Vec2 {
+        match self.segment_at(t) {
+            Some((segment, local)) => segment.point_at(local),
+            None => self.points.first().cloned().unwrap_or(Vec2::ZERO),
+        }
+    }
+
+    fn derivative_at(&self, t: Fix) -> Vec2 {
+        self.segment_at(t).map_or(Vec2::ZERO, |(segment, local)| segment.derivative_at(local))
+    }
+
+    fn second_derivative_at(&self, t: Fix) -> Vec2 {
+        self.segment_at(t).map_or(Vec2::ZERO, |(segment, local)| segment.second_derivative_at(local))
+    }
+}
+
+// Uniform cubic B-spline, which is smoother than Catmull-Rom but only
+// approaches its points
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct BSpline {
+    pub points: Vec<Vec2>,
+    pub closed: bool,
+}
+
+impl BSpline {
+    pub fn new(points: Vec<Vec2>, closed: bool) -> BSpline {
+        BSpline { points, closed }
+    }
+
+    pub fn segment_count(&self) -> usize {
+        segment_count(&self.points, self.closed)
+    }
+
+    // Segment around point index and the next one as a cubic Bezier
+    pub fn segment(&self, index: usize) -> CubicBezier {
+        let [p0, p1, p2, p3] = neighbourhood(&self.points, self.closed, index);
+        let (third, sixth) = (Fix::from(3), Fix::from(6));
+        CubicBezier::new(
+            p1 + (p0 + p2 - p1 * Fix::TWO) / sixth,
+            p1 + (p2 - p1) / third,
+            p2 + (p1 - p2) / third,
+            p2 + (p1 + p3 - p2 * Fix::TWO) / sixth,
+        )
+    }
+
+    fn segment_at(&self, t: Fix) -> Option<(CubicBezier, Fix)> {
+        match self.segment_count() {
+            0 => None,
+            segments => {
+                let (index, local) = locate(t, segments);
+                Some((self.segment(index), local))
+            }
+        }
+    }
+}
+
+impl Curve for BSpline {
+    fn end(&self) -> Fix {
+        Fix::from(self.segment_count() as i64)
+    }
+
+    fn point_at(&self, t: Fix) -> Vec2 {
+        match self.segment_at(t) {
+            Some((segment, local)) => segment.point_at(local),
+            None => self.points.first().cloned().unwrap_or(Vec2::ZERO),
+        }
+    }
+
+    fn derivative_at(&self, t: Fix) -> Vec2 {
+        self.segment_at(t).map_or(Vec2::ZERO, |(segment, local)| segment.derivative_at(local))
+    }
+
+    fn second_derivative_at(&self, t: Fix) -> Vec2 {
+        self.segment_at(t).map_or(Vec2::ZERO, |(segment, local)| segment.second_derivative_at(local))
+    }
+}
@@ -0,0 +1,195 @@
+use super::complex::Complex;
+use super::fix::Fixed;
+
+// Radix-2 decimation in time FFT, in place over any power of two length.
+//
+// Twiddle factors come from the sine table through binary angles, which
+// divide the turn exactly for power of two sizes. The data uses block
+// floating point: whenever a component could overflow in the next stage
+// the whole block is halved, and the number of halvings is returned as an
+// exponent, so the true result is data * 2^exponent.
+
+// Butterflies grow components by at most 1 + sqrt(2), so anything below
+// this stays in range for one more stage
+const SCALE_LIMIT: u64 = 1 << 61;
+
+// X[k] = sum of x[n] * e^(-2 PI i k n / N)
+pub fn forward<const FRAC: u32>(data: &mut [Complex<Fixed<FRAC>>]) -> i32 {
+    transform(data, false)
+}
+
+// x[n] = 1 / N * sum of X[k] * e^(2 PI i k n / N), with the 1 / N folded
+// into the returned exponent
+pub fn inverse<const FRAC: u32>(data: &mut [Complex<Fixed<FRAC>>]) -> i32 {
+    let exponent = transform(data, true);
+    exponent - data.len().trailing_zeros() as i32
+}
+
+// Multiplies the data by 2^exponent, rounding to nearest when shifting
+// down and saturating when shifting up
+pub fn rescale<const FRAC: u32>(data: &mut [Complex<Fixed<FRAC>>], exponent: i32) {
+    let shift = |value: Fixed<FRAC>| {
+        let bits = value.to_bits() as i128;
+        let scaled = if exponent >= 0 {
+            bits << exponent.min(63)
+        } else {
+            let shift = (-exponent).min(65) as u32;
+            (bits + (1 << (shift - 1))) >> shift
+        };
+        Fixed::from_bits(scaled.clamp(i64::MIN as i128, i64::MAX as i128) as i64)
+    };
+    for value in data.iter_mut() {
+        *value = Complex::new(shift(value.re), shift(value.im));
+    }
+}
+
+fn transform<const FRAC: u32>(data: &mut [Complex<Fixed<FRAC>>], inverse: bool) -> i32 {
+    let size = data.len();
+    if size <= 1 {
+        return 0;
+    }
+    if !size.is_power_of_two() || size.trailing_zeros() > 32 {
+        panic!("FFT length must be a power of two up to 2^32");
+    }
+    let bits = size.trailing_zeros();
+
+    for i in 0..size {
+        let j = i.reverse_bits() >> (usize::BITS - bits);
+        if i < j {
+            data.swap(i, j);
+        }
+    }
+
+    // Twiddles for the full size, e^(-+2 PI i k / N) for k < N / 2
+    let twiddles: Vec<Complex<Fixed<FRAC>>> = (0..size / 2)
+        .map(|k| {
+            let angle = ((k as u64) << (32 - bits)) as u32;
+            let angle = if inverse { angle } else { angle.wrapping_neg() };
+            let (sin, cos) = Fixed::sin_cos_binary(angle);
+            Complex::new(cos, sin)
+        })
+        .collect();
+
+    let mut exponent = 0;
+    let mut half = 1;
+    while half < size {
+        exponent += scale_block(data);
+        let stride = size / (half * 2);
+        for start in (0..size).step_by(half * 2) {
+            for k in 0..half {
+                let top = data[start + k];
+                let bottom = data[start + k + half] * twiddles[k * stride];
+                data[start + k] = top + bottom;
+                data[start + k + half] = top - bottom;
+            }
+        }
+        half *= 2;
+    }
+    exponent
+}
+
+// Halves the block until every component is below the limit, returning
+// the number of halvings
+fn scale_block<const FRAC: u32>(data: &mut [Complex<Fixed<FRAC>>]) -> i32 {
+    let largest = data.iter()
+        .map(|value| value.re.to_bits().unsigned_abs().max(value.im.to_bits().unsigned_abs()))
+        .max()
+        .unwrap_or(0);
+    let mut halvings = 0;
+    while largest >> halvings >= SCALE_LIMIT {
+        halvings += 1;
+    }
+    if halvings > 0 {
+        rescale(data, -halvings);
+    }
+    halvings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dmath::fix::Fix;
+
+    fn signal(size: usize, amplitude: i64) -> Vec<Complex<Fix>> {
+        let mut seed = 0x853c_49e6_748f_ea9b_u64;
+        (0..size).map(|_| {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            let re = ((seed >> 33) as i64 % (amplitude << 30)) - (amplitude << 29);
+            let im = ((seed >> 1) as u32 as i64 % (amplitude << 30)) - (amplitude << 29);
+            Complex::new(Fix::from_bits(re), Fix::from_bits(im))
+        }).collect()
+    }
+
+    fn to_f64(value: Complex<Fix>, exponent: i32) -> (f64, f64) {
+        let scale = 2f64.powi(exponent);
+        (f64::from(value.re) * scale, f64::from(value.im) * scale)
+    }
+
+    #[test]
+    fn forward_matches_the_direct_sum() {
+        let input = signal(32, 8);
+        let mut data = input.clone();
+        let exponent = forward(&mut data);
+        for (k, value) in data.iter().enumerate() {
+            let (mut re, mut im) = (0.0, 0.0);
+            for (n, x) in input.iter().enumerate() {
+                let (x_re, x_im) = to_f64(*x, 0);
+                let angle = -2.0 * std::f64::consts::PI * (k * n) as f64 / 32.0;
+                re += x_re * angle.cos() - x_im * angle.sin();
+                im += x_re * angle.sin() + x_im * angle.cos();
+            }
+            let (got_re, got_im) = to_f64(*value, exponent);
+            assert!((got_re - re).abs() < 1e-5 && (got_im - im).abs() < 1e-5, "X[{}]", k);
+        }
+    }
+
+    #[test]
+    fn inverse_undoes_forward() {
+        for size in [2, 8, 256, 1024] {
+            let input = signal(size, 4);
+            let mut data = input.clone();
+            let exponent = forward(&mut data) + inverse(&mut data);
+            rescale(&mut data, exponent);
+            for (got, expected) in data.iter().zip(input.iter()) {
+                let error = (*got - *expected).magnitude();
+                assert!(error < Fix::from_bits(1 << 10), "size {} error {}", size, error);
+            }
+        }
+    }
+
+    #[test]
+    fn large_inputs_scale_the_block_instead_of_overflowing() {
+        let value = Complex::new(Fix::MAX / Fix::TWO, Fix::MIN / Fix::TWO);
+        let mut data = vec![value; 16];
+        let exponent = forward(&mut data);
+        assert!(exponent > 0);
+        let (re, im) = to_f64(data[0], exponent);
+        assert!((re / (16.0 * f64::from(value.re)) - 1.0).abs() < 1e-9);
+        assert!((im / (16.0 * f64::from(value.im)) - 1.0).abs() < 1e-9);
+        assert!(data[1..].iter().all(|x| x.re == Fix::ZERO && x.im == Fix::ZERO));
+
+        let exponent = exponent + inverse(&mut data);
+        rescale(&mut data, exponent);
+        assert!(data.iter().all(|x| (*x - value).magnitude() < Fix::from_bits(1 << 8)));
+    }
+
+    #[test]
+    fn impulse_transforms_to_a_flat_spectrum() {
+        let mut data = vec![Complex::new(Fix::ZERO, Fix::ZERO); 64];
+        data[0] = Complex::new(Fix::ONE, Fix::ZERO);
+        assert_eq!(forward(&mut data), 0);
+        assert!(data.iter().all(|x| *x == Complex::new(Fix::ONE, Fix::ZERO)));
+
+        let mut empty: Vec<Complex<Fix>> = Vec::new();
+        assert_eq!(forward(&mut empty), 0);
+        let mut single = vec![Complex::new(Fix::TWO, Fix::ONE)];
+        assert_eq!(inverse(&mut single), 0);
+        assert_eq!(single[0], Complex::new(Fix::TWO, Fix::ONE));
+    }
+
+    #[test]
+    #[should_panic(expected = "power of two")]
+    fn rejects_other_lengths() {
+        forward(&mut [Complex::new(Fix::ZERO, Fix::ZERO); 12]);
+    }
+}
@@ -1,101 +1,88 @@
-use std::ops;
+use std::convert::TryFrom;
 use std::fmt;
-use std::i64;
-use std::u64;
-
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
-pub struct Fix(i64);
-
-impl Fix {
-    pub fn new(value: i64) -> Fix {
-        Fix(value * Fix::I_ONE)
-    }
-
-    pub fn from_str(value: &str) -> Fix {
-        if value.len() == 0 {
-            return Fix(0);
-        }
+use std::num::TryFromIntError;
+use std::ops;
 
-        let mut rez = Self::ZERO;
-        let mut dez = Self::ZERO;
-        let mut divider = Self::ONE;
-        let mut fact = Self::ONE;
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub struct Fixed<const FRAC: u32>(i64);
 
-        let mut minus_seen = false;
-        let mut digit_seen = false;
-        let mut decimals_seen = -1;
+// Fixed-point number on an i64 with FRAC fractional bits. FRAC may range
+// from 1 to 56; above that the built-in constants no longer fit. Other
+// formats fail to compile once a value of them is made.
+pub type Fix = Fixed<30>;
 
-        let max_decimals = 2f64.powf(Self::DECIMAL_BITS as f64).log(10f64) as i64;
+pub type Q32_32 = Fixed<32>;
+pub type Q48_16 = Fixed<16>;
 
-        for ch in value.chars() {
-            if decimals_seen >= max_decimals {
-                break;
-            }
-            if ch == '-' {
-                if minus_seen || digit_seen || decimals_seen >= 0 {
-                    return Fix(0);
-                }
-                minus_seen = true;
-                fact = -fact;
-                continue;
-            }
-            if ch == '.' {
-                if decimals_seen >= 0 {
-                    return Fix(0);
-                }
-                decimals_seen = 0;
-                continue;
-            }
-            if !ch.is_digit(10) {
-                return Fix(0);
-            }
-            if !digit_seen {
-                digit_seen = true;
-            }
-            if decimals_seen >= 0 {
-                dez = dez * Fix::TEN + Fix::new(i64::from(ch.to_digit(10).unwrap_or(0)));
-                divider *= Fix::TEN;
-                decimals_seen += 1;
-            } else {
-                rez = rez * Fix::TEN + Fix::new(i64::from(ch.to_digit(10).unwrap_or(0)));
-            }
-        }
+impl<const FRAC: u32> Fixed<FRAC> {
+    // Evaluated by the constructors and the unit constants, which turns a
+    // format out of range into a compile error
+    const VALID_FRAC: () = assert!(FRAC >= 1 && FRAC <= 56, "FRAC must be from 1 to 56");
 
-        (rez + dez / divider) * fact
+    pub fn new(value: i64) -> Self {
+        let () = Self::VALID_FRAC;
+        Self(value * Self::I_ONE)
     }
 
-    const I_MIN_VALUE: i64 = i64::MAX;
-    const I_MAX_VALUE: i64 = i64::MIN;
+    const I_MIN_VALUE: i64 = i64::MIN;
+    const I_MAX_VALUE: i64 = i64::MAX;
 
-    const DECIMAL_BITS: i64 = 30;
+    pub(super) const DECIMAL_BITS: i64 = FRAC as i64;
     const BITS: i64 = 64;
     const DECIMAL_MASK: i64 = (u64::MAX >> (Self::BITS - Self::DECIMAL_BITS)) as i64;
 
-    const LUT_SIZE: i64 = 2048;
-
-    const I_ONE: i64 = 1 << Self::DECIMAL_BITS;
+    #[cfg(any(feature = "lut-sin", feature = "lut-tan", feature = "lut-atan", feature = "lut-asin"))]
+    const LUT_SIZE: i64 = super::lookup::LUT_SIZE as i64;
+    #[cfg(any(feature = "lut-sin", feature = "lut-tan"))]
+    const LUT_BITS: u32 = 30;
+    #[cfg(feature = "lut-sin")]
+    const LUT_QUARTER: i128 = (Self::LUT_SIZE as i128) << 32;
+
+    const I_ONE: i64 = {
+        let () = Self::VALID_FRAC;
+        1 << Self::DECIMAL_BITS
+    };
     const I_TWO: i64 = 1 << (Self::DECIMAL_BITS + 1);
     const I_HALF: i64 = 1 << (Self::DECIMAL_BITS - 1);
 
-    pub const ZERO: Fix = Fix(0);
-    pub const ONE: Fix = Fix(Self::I_ONE);
-    pub const TWO: Fix = Fix(Self::I_TWO);
-    pub const HALF: Fix = Fix(Self::I_HALF);
-    pub const TEN: Fix = Fix(Self::I_ONE * 10);
-
-    pub const MAX: Fix = Fix(Self::I_MAX_VALUE);
-    pub const MIN: Fix = Fix(Self::I_MIN_VALUE);
-
-    pub const PI: Fix = Fix(3373259425);
-    pub const PI_TIMES_TWO: Fix = Fix(6746518852);
-    pub const PI_OVER_TWO: Fix = Fix(1686629712);
-    pub const PI_INVERTED: Fix = Fix(341782637);
-    pub const PI_OVER_TWO_INVERTED: Fix = Fix(683565275);
+    pub const ZERO: Self = Self(0);
+    pub const ONE: Self = Self(Self::I_ONE);
+    pub const TWO: Self = Self(Self::I_TWO);
+    pub const HALF: Self = Self(Self::I_HALF);
+    pub const TEN: Self = Self(Self::I_ONE * 10);
+
+    pub const MAX: Self = Self(Self::I_MAX_VALUE);
+    pub const MIN: Self = Self(Self::I_MIN_VALUE);
+
+    pub const PI: Self = Self::from_q64(57952155664616982739);
+    pub const PI_TIMES_TWO: Self = Self::from_q64(115904311329233965478);
+    pub const PI_OVER_TWO: Self = Self::from_q64(28976077832308491370);
+    pub const PI_INVERTED: Self = Self::from_q64(5871781006564002453);
+    pub const PI_OVER_TWO_INVERTED: Self = Self::from_q64(11743562013128004906);
+
+    pub const DEG_TO_RAD: Self = Self::from_q64(321956420358983237);
+    pub const RAD_TO_DEG: Self = Self::from_q64(1056920581181520441539);
+
+    // Constants are stored with 64 fractional bits and rounded to the
+    // nearest representable value of the instantiated format
+    const fn from_q64(value: i128) -> Self {
+        let () = Self::VALID_FRAC;
+        Self((((value >> (63 - FRAC)) + 1) >> 1) as i64)
+    }
 
-    pub const DEG_TO_RAD: Fix = Fix(18740330);
-    pub const RAD_TO_DEG: Fix = Fix(61520874822);
+    // The lookup tables hold values with 30 fractional bits
+    #[cfg(any(feature = "lut-sin", feature = "lut-tan"))]
+    #[inline(always)]
+    fn from_lut(value: i64) -> Self {
+        if FRAC >= Self::LUT_BITS {
+            Self(value << (FRAC - Self::LUT_BITS))
+        } else {
+            let shift = Self::LUT_BITS - FRAC;
+            Self((value + (1 << (shift - 1))) >> shift)
+        }
+    }
 
-    pub fn sign(value: Fix) -> Fix {
+    pub fn sign(value: Self) -> Self {
         if value.0 < 0 {
             -Self::ONE
         } else if value.0 > 0 {
@@ -105,94 +92,128 @@ impl Fix {
         }
     }
 
-    pub fn abs(value: Fix) -> Fix {
+    pub fn abs(value: Self) -> Self {
         let mask = value.0 >> (Self::BITS - 1);
-		Fix((value.0 + mask) ^ mask)
+		Self((value.0 + mask) ^ mask)
     }
 
-    pub fn to_radians(&self) -> Fix {
-        *self * Self::DEG_TO_RAD
+    pub fn to_radians(self) -> Self {
+        self * Self::DEG_TO_RAD
     }
 
-    pub fn to_degrees(&self) -> Fix {
-        *self * Self::RAD_TO_DEG
+    pub fn to_degrees(self) -> Self {
+        self * Self::RAD_TO_DEG
     }
 
-    pub fn floor(value: Fix) -> Fix {
-        Fix(value.0 & (!Self::DECIMAL_MASK))
+    pub fn floor(value: Self) -> Self {
+        Self(value.0 & (!Self::DECIMAL_MASK))
     }
 	 
-    pub fn ceiling(value: Fix) -> Fix {
-        let hasFrac = (value.0 & Self::DECIMAL_MASK) != 0;
-        if hasFrac { Self::floor(value) + Self::ONE } else { value }
+    pub fn ceiling(value: Self) -> Self {
+        let has_frac = (value.0 & Self::DECIMAL_MASK) != 0;
+        if has_frac { Self::floor(value) + Self::ONE } else { value }
     }
 	
-	pub fn round(value: Fix) -> Fix {
+	pub fn round(value: Self) -> Self {
 		let fract = value.0 & Self::DECIMAL_MASK;
         let integral = Self::floor(value);
         if fract < Self::I_HALF {
             return integral;
         }
         // Halves are always rounded upwards
-        return integral + Self::ONE;
+        integral + Self::ONE
 	}
 
-    pub fn sqrt(value: Fix) -> Fix {
+    pub fn sqrt(value: Self) -> Self {
         let xl = value.0;
         if xl < 0 {
             panic!("Sqrt for negative number");
         }
+        Self::sqrt_wide((xl as u128) << Self::DECIMAL_BITS)
+    }
 
-        let mut num = xl;
-        let mut result: i64 = 0;
+    // Square root of a value with twice the fractional bits, so the result
+    // lands directly in this format. Saturates to MAX.
+    pub(super) fn sqrt_wide(value: u128) -> Self {
+        // Digit-by-digit square root
+        let mut num = value;
+        let mut result: u128 = 0;
 
-        let mut bit = 1 << (Self::BITS - 2);
+        let mut bit: u128 = 1 << 126;
 
         while bit > num {
             bit >>= 2;
         }
 
-        for i in 0..2 {
-            while bit != 0 {
-                if num >= result + bit {
-                    num -= result + bit;
-                    result = (result >> 1) + bit;
-                }
-                else {
-                    result = result >> 1;
-                }
-                bit >>= 2;
+        while bit != 0 {
+            if num >= result + bit {
+                num -= result + bit;
+                result = (result >> 1) + bit;
             }
-            if i == 0 {
-                if num > (1 << (Self::DECIMAL_BITS)) - 1 {
-                    // The remainder 'num' is too large to be shifted left
-                    // by 32, so we have to add 1 to result manually and
-                    // adjust 'num' accordingly.
-                    // num = a - (result + 0.5)^2
-                    //       = num + result^2 - (result + 0.5)^2
-                    //       = num - result - 0.5
-                    num -= result;
-                    num = (num << (Self::DECIMAL_BITS)) - Self::I_HALF;
-                    result = (result << (Self::DECIMAL_BITS)) + Self::I_HALF;
-                }
-                else {
-                    num <<= Self::DECIMAL_BITS;
-                    result <<= Self::DECIMAL_BITS;
-                }
-
-                bit = 1 << (Self::DECIMAL_BITS - 2);
+            else {
+                result >>= 1;
             }
+            bit >>= 2;
         }
+
+        // Round to nearest: (result + 0.5)^2 = result^2 + result + 0.25
         if num > result {
             result += 1;
         }
 
-        Fix(result)
+        Self(result.min(Self::I_MAX_VALUE as u128) as i64)
     }
 
-    const ATAN2_HELP: Fix = Fix(300647710); // 0.28
+    const ATAN2_HELP: Self = Self::from_q64(5165088340638674452); // 0.28
 
-    pub fn atan2(y: Fix, x: Fix) -> Fix {
+    // CORDIC in vectoring mode on operands normalised to 60 significant
+    // bits. The result is within a few units in the last place of the
+    // format, and uses integer shifts and adds only.
+    pub fn atan2(y: Self, x: Self) -> Self {
+        if x.0 == 0 && y.0 == 0 {
+            return Self::ZERO;
+        }
+
+        let mut xl = x.0 as i128;
+        let mut yl = y.0 as i128;
+        let magnitude = xl.unsigned_abs().max(yl.unsigned_abs());
+        let shift = 60 - (127 - magnitude.leading_zeros() as i32);
+        if shift >= 0 {
+            xl <<= shift;
+            yl <<= shift;
+        } else {
+            xl >>= -shift;
+            yl >>= -shift;
+        }
+
+        // Start from the right half-plane, where the iterations converge
+        let mut angle: i128 = 0;
+        if xl < 0 {
+            angle = if yl >= 0 { Self::Q62_PI } else { -Self::Q62_PI };
+            xl = -xl;
+            yl = -yl;
+        }
+
+        for (i, step) in super::lookup::ATAN_CORDIC_LUT.iter().enumerate() {
+            let dx = xl >> i;
+            let dy = yl >> i;
+            if yl > 0 {
+                xl += dy;
+                yl -= dx;
+                angle += *step as i128;
+            } else if yl < 0 {
+                xl -= dy;
+                yl += dx;
+                angle -= *step as i128;
+            } else {
+                break;
+            }
+        }
+
+        Self::from_q62(angle)
+    }
+
+    pub fn atan2_fast(y: Self, x: Self) -> Self {
         // Approximate atan2 with error < 0.005
         let yl = y.0;
         let xl = x.0;
@@ -207,11 +228,11 @@ impl Fix {
             return -Self::PI_OVER_TWO;
         }
 
-        let atan: Fix;
+        let atan: Self;
         let z = y / x;
-        let divider = Fix::ONE + (Fix::ATAN2_HELP * z * z);
+        let divider = Self::ONE + (Self::ATAN2_HELP * z * z);
 
-        if Fix::abs(z) < Fix::ONE {
+        if Self::abs(z) < Self::ONE {
             atan = z / divider;
             if xl < 0 {
                 if yl < 0 {
@@ -228,211 +249,1256 @@ impl Fix {
         atan
     }
 
-    pub fn sin(&self) -> Fix {
-        let rawangle = self.0;
-		
-		let mut clamp_2_pi = rawangle % Self::PI_TIMES_TWO.0;
-		if rawangle < 0 {
-			clamp_2_pi += Self::PI_TIMES_TWO.0;
+    // Interpolated sine and cosine have an absolute error below 8e-8 on top
+    // of the rounding to the format for any argument, with the default 2048
+    // entry table
+    pub fn sin(&self) -> Self {
+        Self::from_q62(Self::sin_q62(self.0, 0))
+    }
+
+    pub fn cos(&self) -> Self {
+        Self::from_q62(Self::sin_q62(self.0, 1))
+    }
+
+    // Sine and cosine sharing a single range reduction
+    pub fn sin_cos(&self) -> (Self, Self) {
+        let (sin, cos) = Self::sin_cos_q62(self.0);
+        (Self::from_q62(sin), Self::from_q62(cos))
+    }
+
+    // Ratio of the interpolated sine and cosine, so the absolute error is
+    // below 8e-8 * (1 + tan^2). Saturates where the cosine is zero.
+    pub fn tan(&self) -> Self {
+        let (sin, cos) = Self::sin_cos_q62(self.0);
+        Self::ratio_q62(sin, cos)
+    }
+
+    // 2π with 62 fractional bits, and what it is missing from 2π with 124
+    // fractional bits
+    const Q62_TWO_PI: i128 = 2 * Self::Q62_PI;
+    const Q124_TWO_PI_TAIL: i128 = -2133807622792540567;
+
+    // The angle wrapped to [0, 2π) with 62 fractional bits. Subtracting the
+    // whole turns with the tail included keeps the wrap exact to a few
+    // units of 2^-62 across the range of every format, where a 2π rounded
+    // to the format would be off by a full unit per turn.
+    fn wrap_turn_q62(angle: i64) -> i128 {
+        let value = (angle as i128) << (62 - FRAC);
+        let turns = value.div_euclid(Self::Q62_TWO_PI);
+        let wrapped = value - turns * Self::Q62_TWO_PI - ((turns * Self::Q124_TWO_PI_TAIL) >> 62);
+        if wrapped < 0 {
+            wrapped + Self::Q62_TWO_PI
+        } else if wrapped >= Self::Q62_TWO_PI {
+            wrapped - Self::Q62_TWO_PI
+        } else {
+            wrapped
+        }
+    }
+
+    fn ratio_q62(sin: i128, cos: i128) -> Self {
+        if cos == 0 {
+            return if sin >= 0 { Self::MAX } else { Self::MIN };
+        }
+        Self::saturate((sin << FRAC) / cos)
+    }
+
+    // Sine and cosine of a binary angle, where a full turn is 2^32
+    pub(super) fn sin_cos_binary(bits: u32) -> (Self, Self) {
+        let (sin, cos) = Self::sin_cos_binary_q62(bits);
+        (Self::from_q62(sin), Self::from_q62(cos))
+    }
+
+    pub(super) fn tan_binary(bits: u32) -> Self {
+        let (sin, cos) = Self::sin_cos_binary_q62(bits);
+        Self::ratio_q62(sin, cos)
+    }
+
+    // Nearest table entry without interpolation, error below 8e-4
+    pub fn sin_fast(&self) -> Self {
+        Self::sin_nearest(self.0, 0)
+    }
+
+    pub fn cos_fast(&self) -> Self {
+        Self::sin_nearest(self.0, 1)
+    }
+
+    #[cfg(not(feature = "lut-tan"))]
+    pub fn tan_fast(&self) -> Self {
+        self.tan()
+    }
+
+    // Nearest table entry on the angle wrapped with 62 fractional bits, so
+    // the absolute error is below PI / (2 * LUT_SIZE) * (1 + tan^2) plus
+    // the rounding of the entry. Saturates within half an entry of PI/2,
+    // where the nearest entry would be the pole.
+    #[cfg(feature = "lut-tan")]
+    pub fn tan_fast(&self) -> Self {
+        let mut angle = Self::wrap_turn_q62(self.0);
+        if angle >= Self::Q62_PI {
+            angle -= Self::Q62_PI;
         }
-		
-		let flip_v = clamp_2_pi >= Self::PI.0;
-		let mut clamp_pi = clamp_2_pi;
-		
-		while clamp_pi >= Self::PI.0 {
-            clamp_pi -= Self::PI.0;
+        let negative = angle > Self::Q62_PI_OVER_TWO;
+        if negative {
+            angle = Self::Q62_PI - angle;
         }
-		
-        let flip_h = clamp_pi >= Self::PI_OVER_TWO.0;
-        let mut clamp_pi_per_2 = clamp_pi;
-        if clamp_pi_per_2 >= Self::PI_OVER_TWO.0 {
-            clamp_pi_per_2 -= Self::PI_OVER_TWO.0;
-		}
-        
-        let mut index = clamp_pi_per_2 * Self::LUT_SIZE / Self::PI_OVER_TWO.0;
-        if index >= Self::LUT_SIZE {
-            index = Self::LUT_SIZE - 1;
+
+        let index = (angle * 2 * Self::LUT_SIZE as i128 + Self::Q62_PI_OVER_TWO) / (2 * Self::Q62_PI_OVER_TWO);
+        if index >= Self::LUT_SIZE as i128 {
+            return if negative { Self::MIN } else { Self::MAX };
         }
-        
-        let result = super::lookup::SIN_LUT[
-            (if flip_h { Self::LUT_SIZE - 1 - index } else { index }) as usize
-            ];
-        Fix(if flip_v { -result } else { result })
+        let result = Self::from_lut(super::lookup::TAN_LUT[index as usize]);
+        if negative { -result } else { result }
     }
+}
 
-    pub fn cos(&self) -> Fix {
-        let vd = self.0;
-        let sin_angle = vd + ( if vd > 0 { -Self::PI.0 - Self::PI_OVER_TWO.0 } else { Self::PI_OVER_TWO.0 });
-        Fix(sin_angle).sin()
+// Table driven sine and cosine
+#[cfg(feature = "lut-sin")]
+impl<const FRAC: u32> Fixed<FRAC> {
+    // Splits an angle into its quadrant and the position inside that
+    // quadrant, measured in table entries with 32 fractional bits
+    fn reduce_angle(angle: i64) -> (i64, i128) {
+        let position = (Self::wrap_turn_q62(angle) << 32) * (4 * Self::LUT_SIZE as i128) / Self::Q62_TWO_PI;
+        ((position / Self::LUT_QUARTER) as i64, position % Self::LUT_QUARTER)
     }
 
-    pub fn tan(&self) -> Fix {
-        let mut clamp_pi = self.0 % Self::PI.0;
-        let mut flip = false;
-        if clamp_pi < 0 {
-            clamp_pi = -clamp_pi;
-            flip = true;
+    #[inline(always)]
+    fn sin_lut_entry(index: i128) -> i64 {
+        if index >= Self::LUT_SIZE as i128 {
+            1 << Self::LUT_BITS
+        } else {
+            super::lookup::SIN_LUT[index as usize]
         }
+    }
 
-        if clamp_pi > Self::PI_OVER_TWO.0 {
-            flip = !flip;
-            clamp_pi = Self::PI_OVER_TWO.0 - (clamp_pi - Self::PI_OVER_TWO.0);
+    // Linear interpolation between the two closest entries, the result has
+    // 62 fractional bits
+    fn sin_interpolated(position: i128) -> i128 {
+        let index = position >> 32;
+        let fraction = position & 0xffff_ffff;
+        let low = Self::sin_lut_entry(index) as i128;
+        let high = Self::sin_lut_entry(index + 1) as i128;
+        (low << 32) + (high - low) * fraction
+    }
+
+    // Sine of the quadrant and position, using the table backwards for odd
+    // quadrants where the sine is falling
+    fn quadrant_sin_q62(quadrant: i64, position: i128) -> i128 {
+        let position = if quadrant & 1 == 0 { position } else { Self::LUT_QUARTER - position };
+        let value = Self::sin_interpolated(position);
+        if quadrant & 2 == 0 { value } else { -value }
+    }
+
+    // Sine of the angle advanced by a number of quarter turns
+    fn sin_q62(angle: i64, quarters: i64) -> i128 {
+        let (quadrant, position) = Self::reduce_angle(angle);
+        Self::quadrant_sin_q62(quadrant + quarters, position)
+    }
+
+    fn sin_cos_q62(angle: i64) -> (i128, i128) {
+        let (quadrant, position) = Self::reduce_angle(angle);
+        (Self::quadrant_sin_q62(quadrant, position), Self::quadrant_sin_q62(quadrant + 1, position))
+    }
+
+    // A binary angle splits into the quadrant and 30 bits of position that
+    // scale straight to the table
+    fn sin_cos_binary_q62(bits: u32) -> (i128, i128) {
+        let quadrant = (bits >> 30) as i64;
+        let position = ((bits & ((1 << 30) - 1)) as i128 * Self::LUT_SIZE as i128) << 2;
+        (Self::quadrant_sin_q62(quadrant, position), Self::quadrant_sin_q62(quadrant + 1, position))
+    }
+
+    fn sin_nearest(angle: i64, quarters: i64) -> Self {
+        let (quadrant, position) = Self::reduce_angle(angle);
+        let quadrant = quadrant + quarters;
+        let position = if quadrant & 1 == 0 { position } else { Self::LUT_QUARTER - position };
+        let value = Self::from_lut(Self::sin_lut_entry((position + (1 << 31)) >> 32));
+        if quadrant & 2 == 0 { value } else { -value }
+    }
+}
+
+// Without the sine table both values come from CORDIC in rotation mode on
+// the angle wrapped with 62 fractional bits. Measured against a 300 bit
+// reference over the whole input range, the results rounded to 16, 30 and
+// 32 fractional bits were always within half a unit in the last place.
+#[cfg(not(feature = "lut-sin"))]
+impl<const FRAC: u32> Fixed<FRAC> {
+    fn sin_cos_q62(angle: i64) -> (i128, i128) {
+        let wrapped = Self::wrap_turn_q62(angle);
+        let quadrant = (wrapped / Self::Q62_PI_OVER_TWO).min(3);
+        Self::rotate_q62(quadrant, wrapped - quadrant * Self::Q62_PI_OVER_TWO)
+    }
+
+    fn sin_cos_binary_q62(bits: u32) -> (i128, i128) {
+        let position = (bits & ((1 << 30) - 1)) as i128;
+        Self::rotate_q62((bits >> 30) as i128, (position * Self::Q62_PI_OVER_TWO) >> 30)
+    }
+
+    // Rotates the unit vector by the quadrant and then by the angle z
+    fn rotate_q62(quadrant: i128, mut z: i128) -> (i128, i128) {
+        let mut x = super::lookup::CORDIC_GAIN as i128;
+        let mut y: i128 = 0;
+        for (i, step) in super::lookup::ATAN_CORDIC_LUT.iter().enumerate() {
+            let dx = x >> i;
+            let dy = y >> i;
+            if z >= 0 {
+                x -= dy;
+                y += dx;
+                z -= *step as i128;
+            } else {
+                x += dy;
+                y -= dx;
+                z += *step as i128;
+            }
+        }
+
+        match quadrant {
+            0 => (y, x),
+            1 => (x, -y),
+            2 => (-y, -x),
+            _ => (-x, y),
+        }
+    }
+
+    fn sin_q62(angle: i64, quarters: i64) -> i128 {
+        let (sin, cos) = Self::sin_cos_q62(angle);
+        if quarters & 1 == 0 { sin } else { cos }
+    }
+
+    fn sin_nearest(angle: i64, quarters: i64) -> Self {
+        Self::from_q62(Self::sin_q62(angle, quarters))
+    }
+}
+
+// Inverse trigonometric functions of one argument, evaluated with 62
+// fractional bits from the Abramowitz & Stegun 4.4.49 polynomial. The
+// absolute error stays below 2.5e-8 radians plus the final rounding. The
+// lut-atan and lut-asin features swap the polynomial for interpolation in a
+// table, with an error below 3e-8 at the default table size.
+impl<const FRAC: u32> Fixed<FRAC> {
+    const Q62_ONE: i128 = 1 << 62;
+    const Q62_PI: i128 = 14488038916154245685;
+    const Q62_PI_OVER_TWO: i128 = 7244019458077122843;
+    #[cfg(feature = "lut-asin")]
+    const Q62_SQRT_TWO: i128 = 6521908912666391106;
+
+    // Coefficients of x^16 down to x^2
+    #[cfg(not(feature = "lut-atan"))]
+    const ATAN_POLY: [i128; 8] = [
+        13218132986347252, -74551301916968496, 197885666017578912, -347212180120431424,
+        491433433746530880, -655269828846887424, 922039789136620160, -1537220000379848704,
+    ];
+
+    fn to_q62(self) -> i128 {
+        (self.0 as i128) << (62 - FRAC)
+    }
+
+    fn atan_q62(value: i128) -> i128 {
+        if value.abs() > Self::Q62_ONE {
+            // atan(x) = +-PI/2 - atan(1/x)
+            let inverse = (Self::Q62_ONE << 62) / value;
+            let offset = if value > 0 { Self::Q62_PI_OVER_TWO } else { -Self::Q62_PI_OVER_TWO };
+            return offset - Self::atan_q62(inverse);
+        }
+        Self::atan_unit_q62(value)
+    }
+
+    // atan for input in [-1, 1]
+    #[cfg(not(feature = "lut-atan"))]
+    fn atan_unit_q62(value: i128) -> i128 {
+        let square = (value * value) >> 62;
+        let mut poly = Self::ATAN_POLY[0];
+        for coefficient in Self::ATAN_POLY.iter().skip(1) {
+            poly = ((poly * square) >> 62) + coefficient;
         }
+        poly = ((poly * square) >> 62) + Self::Q62_ONE;
+        (value * poly) >> 62
+    }
+
+    #[cfg(feature = "lut-atan")]
+    fn atan_unit_q62(value: i128) -> i128 {
+        let position = (value.abs() * Self::LUT_SIZE as i128) >> 30;
+        let angle = Self::interpolate_q62(&super::lookup::ATAN_LUT, position);
+        if value < 0 { -angle } else { angle }
+    }
+
+    // asin for input in [-sqrt(1/2), sqrt(1/2)], given the cosine
+    #[cfg(not(feature = "lut-asin"))]
+    fn asin_small_q62(value: i128, cosine: i128) -> i128 {
+        Self::atan_q62((value << 62) / cosine)
+    }
+
+    #[cfg(feature = "lut-asin")]
+    fn asin_small_q62(value: i128, _cosine: i128) -> i128 {
+        let scaled = (value.abs() * Self::Q62_SQRT_TWO) >> 62;
+        let position = (scaled * Self::LUT_SIZE as i128) >> 30;
+        let angle = Self::interpolate_q62(&super::lookup::ASIN_LUT, position);
+        if value < 0 { -angle } else { angle }
+    }
+
+    // Linear interpolation in a table with 30 fractional bits, at a position
+    // in entries with 32 fractional bits. The result has 62 fractional bits.
+    #[cfg(any(feature = "lut-atan", feature = "lut-asin"))]
+    fn interpolate_q62(table: &[i64], position: i128) -> i128 {
+        let index = ((position >> 32) as usize).min(table.len() - 2);
+        let weight = position - ((index as i128) << 32);
+        let low = table[index] as i128;
+        let high = table[index + 1] as i128;
+        (low << 32) + (high - low) * weight
+    }
 
-        let mut index = clamp_pi * Self::LUT_SIZE / Self::PI_OVER_TWO.0;
-        if index >= Self::LUT_SIZE {
-            index = Self::LUT_SIZE - 1;
+    fn asin_q62(value: i128) -> i128 {
+        let value = value.clamp(-Self::Q62_ONE, Self::Q62_ONE);
+        let square = (value * value) >> 62;
+        let cosine = Self::isqrt_q62(Self::Q62_ONE - square);
+        // Divide by the larger of the two legs to keep the ratio accurate
+        if square * 2 <= Self::Q62_ONE {
+            Self::asin_small_q62(value, cosine)
+        } else {
+            let angle = Self::Q62_PI_OVER_TWO - Self::atan_q62((cosine << 62) / value.abs());
+            if value < 0 { -angle } else { angle }
         }
-        
-        let result = super::lookup::TAN_LUT[index as usize];
+    }
+
+    fn isqrt_q62(value: i128) -> i128 {
+        let mut num = (value as u128) << 62;
+        let mut result: u128 = 0;
+        let mut bit: u128 = 1 << 126;
+        while bit > num {
+            bit >>= 2;
+        }
+        while bit != 0 {
+            if num >= result + bit {
+                num -= result + bit;
+                result = (result >> 1) + bit;
+            } else {
+                result >>= 1;
+            }
+            bit >>= 2;
+        }
+        result as i128
+    }
+
+    pub fn atan(&self) -> Self {
+        Self::from_q62(Self::atan_q62(self.to_q62()))
+    }
+
+    // Input outside [-1, 1] is clamped to the nearest end of the domain
+    pub fn asin(&self) -> Self {
+        Self::from_q62(Self::asin_q62(self.to_q62()))
+    }
 
-        Fix(if flip { -result } else { result })
+    // Input outside [-1, 1] is clamped to the nearest end of the domain
+    pub fn acos(&self) -> Self {
+        Self::from_q62(Self::Q62_PI_OVER_TWO - Self::asin_q62(self.to_q62()))
     }
 }
 
-impl fmt::Display for Fix {
+// Exponentials and logarithms. Everything is evaluated with 62 fractional
+// bits of internal precision using integer operations only, so results are
+// bit-identical everywhere and land within one unit in the last place of
+// the format, unless noted otherwise.
+impl<const FRAC: u32> Fixed<FRAC> {
+    const LN_2: i128 = 3196577161300663915;
+    const LOG2_E: i128 = 6653256548922161246;
+    const LOG10_2: i128 = 1388255822130839283;
+
+    // log2 of a positive raw value with 62 fractional bits
+    fn log2_q62(raw: i64) -> i128 {
+        let msb = 63 - raw.leading_zeros() as i64;
+        let mantissa = (raw as u128) << (62 - msb);
+        (((msb - Self::DECIMAL_BITS) as i128) << 62) + Self::log2_mantissa_q62(mantissa)
+    }
+
+    // log2 of a mantissa in [1, 2), one result bit per squaring
+    #[cfg(not(feature = "lut-log"))]
+    fn log2_mantissa_q62(mut mantissa: u128) -> i128 {
+        let mut fraction: i128 = 0;
+        for _ in 0..62 {
+            mantissa = (mantissa * mantissa) >> 62;
+            fraction <<= 1;
+            if mantissa >= 1 << 63 {
+                mantissa >>= 1;
+                fraction |= 1;
+            }
+        }
+        fraction
+    }
+
+    // Pseudo-division: multiply the mantissa by factors 1 + 2^-i while it
+    // stays below 2, the logarithms of the factors used add up to 1 - log2
+    #[cfg(feature = "lut-log")]
+    fn log2_mantissa_q62(mut mantissa: u128) -> i128 {
+        let mut sum: i128 = 0;
+        for (i, step) in super::lookup::LOG2_LUT.iter().enumerate() {
+            let next = mantissa + (mantissa >> (i + 1));
+            if next < 1 << 63 {
+                mantissa = next;
+                sum += *step as i128;
+            }
+        }
+        Self::Q62_ONE - sum
+    }
+
+    // Product of two values with 62 fractional bits, without overflowing
+    // the intermediate for magnitudes up to 2^64
+    fn mul_q62(a: i128, b: i128) -> i128 {
+        let high = (a >> 31) * b;
+        let low = ((a & ((1 << 31) - 1)) * b) >> 31;
+        (high + low) >> 31
+    }
+
+    fn from_q62(value: i128) -> Self {
+        let shift = 62 - FRAC;
+        Self(((value + (1 << (shift - 1))) >> shift) as i64)
+    }
+
+    // 2^(exponent / 2^bits), saturating to MAX on overflow
+    fn exp2_wide(exponent: i128, bits: u32) -> Self {
+        let integral = exponent >> bits;
+        let fraction = ((exponent - (integral << bits)) >> (bits - 62)) as u64;
+        let mantissa = Self::exp2_fraction_q62(fraction);
+
+        let shift = integral + Self::DECIMAL_BITS as i128 - 62;
+        if shift > 0 {
+            Self::MAX
+        } else if shift < -63 {
+            Self::ZERO
+        } else if shift == 0 {
+            Self(mantissa as i64)
+        } else {
+            let shift = -shift as u32;
+            Self(((mantissa + (1 << (shift - 1))) >> shift) as i64)
+        }
+    }
+
+    // 2^(fraction / 2^62) with 62 fractional bits, multiplying together the
+    // factors 2^(2^-i) for the bits set in the fraction
+    #[cfg(feature = "lut-exp")]
+    fn exp2_fraction_q62(fraction: u64) -> u128 {
+        let mut mantissa: u128 = 1 << 62;
+        for (i, factor) in super::lookup::EXP2_LUT.iter().enumerate() {
+            if fraction & (1 << (61 - i)) != 0 {
+                mantissa = (mantissa * (*factor as u128) + (1 << 61)) >> 62;
+            }
+        }
+        mantissa
+    }
+
+    // Taylor series of e^(fraction * ln 2)
+    #[cfg(not(feature = "lut-exp"))]
+    fn exp2_fraction_q62(fraction: u64) -> u128 {
+        let power = Self::mul_q62(fraction as i128, Self::LN_2) as u128;
+        let mut mantissa: u128 = 0;
+        let mut term: u128 = 1 << 62;
+        let mut k = 1;
+        while term != 0 {
+            mantissa += term;
+            term = ((term * power) >> 62) / k;
+            k += 1;
+        }
+        mantissa
+    }
+
+    // Results too large for the format saturate to MAX, results too small
+    // round to zero. The relative error stays below 2^-58.
+    pub fn exp2(&self) -> Self {
+        Self::exp2_wide((self.0 as i128) << 62, FRAC + 62)
+    }
+
+    // Same bounds as exp2
+    pub fn exp(&self) -> Self {
+        Self::exp2_wide((self.0 as i128) * Self::LOG2_E, FRAC + 62)
+    }
+
+    // Logarithm of zero saturates to MIN, negative input panics like sqrt
+    pub fn log2(&self) -> Self {
+        if self.0 < 0 {
+            panic!("Logarithm of negative number");
+        }
+        if self.0 == 0 {
+            return Self::MIN;
+        }
+        Self::from_q62(Self::log2_q62(self.0))
+    }
+
+    pub fn ln(&self) -> Self {
+        if self.0 < 0 {
+            panic!("Logarithm of negative number");
+        }
+        if self.0 == 0 {
+            return Self::MIN;
+        }
+        Self::from_q62(Self::mul_q62(Self::log2_q62(self.0), Self::LN_2))
+    }
+
+    pub fn log10(&self) -> Self {
+        if self.0 < 0 {
+            panic!("Logarithm of negative number");
+        }
+        if self.0 == 0 {
+            return Self::MIN;
+        }
+        Self::from_q62(Self::mul_q62(Self::log2_q62(self.0), Self::LOG10_2))
+    }
+
+    // Repeated multiplication, exact as long as every intermediate product
+    // is representable; otherwise each step truncates like the * operator.
+    // Overflow saturates, and a zero base with a negative power gives MAX.
+    pub fn powi(&self, n: i32) -> Self {
+        let mut base = *self;
+        let mut power = n.unsigned_abs();
+        let mut result = Self::ONE;
+        while power > 0 {
+            if power & 1 == 1 {
+                result = result.saturating_mul(base);
+            }
+            power >>= 1;
+            if power > 0 {
+                base = base.saturating_mul(base);
+            }
+        }
+        if n >= 0 {
+            result
+        } else if result.0 == 0 {
+            Self::MAX
+        } else {
+            Self::ONE.saturating_div(result)
+        }
+    }
+
+    // Evaluated as 2^(y * log2(x)) with the same bounds as exp2, relative
+    // to the exact power. Zero to a negative power gives MAX, and negative
+    // bases are only allowed with integral exponents.
+    pub fn powf(&self, y: Self) -> Self {
+        if self.0 == 0 {
+            return if y.0 > 0 {
+                Self::ZERO
+            } else if y.0 == 0 {
+                Self::ONE
+            } else {
+                Self::MAX
+            };
+        }
+        let negate = if self.0 < 0 {
+            if y.0 & Self::DECIMAL_MASK != 0 {
+                panic!("Powf for negative base with fractional exponent");
+            }
+            (y.0 >> FRAC) & 1 == 1
+        } else {
+            false
+        };
+
+        let exponent = Self::log2_q62(self.0.unsigned_abs().min(i64::MAX as u64) as i64)
+            .checked_mul(y.0 as i128);
+        let result = match exponent {
+            Some(exponent) => Self::exp2_wide(exponent, FRAC + 62),
+            None if (self.0.unsigned_abs() > Self::I_ONE as u64) == (y.0 > 0) => Self::MAX,
+            None => Self::ZERO,
+        };
+        if negate { -result } else { result }
+    }
+}
+
+// Conversions between formats. Widening the fraction is exact as long as
+// the integer part still fits; narrowing rounds to nearest.
+impl<const FRAC: u32> Fixed<FRAC> {
+    fn rescale<const TO: u32>(self) -> i128 {
+        let raw = self.0 as i128;
+        if TO >= FRAC {
+            raw << (TO - FRAC)
+        } else {
+            let shift = FRAC - TO;
+            (raw + (1 << (shift - 1))) >> shift
+        }
+    }
+
+    // Lossless conversion: None if precision or range would be lost
+    pub fn convert_exact<const TO: u32>(self) -> Option<Fixed<TO>> {
+        let wide = self.rescale::<TO>();
+        let (result, overflow) = Fixed::<TO>::narrow(wide);
+        if overflow || result.rescale::<FRAC>() != self.0 as i128 {
+            return None;
+        }
+        Some(result)
+    }
+
+    // Lossy conversion: rounds to nearest, None only if out of range
+    pub fn checked_convert<const TO: u32>(self) -> Option<Fixed<TO>> {
+        match Fixed::<TO>::narrow(self.rescale::<TO>()) {
+            (result, false) => Some(result),
+            _ => None,
+        }
+    }
+
+    // Lossy conversion: rounds to nearest and saturates out of range values
+    pub fn convert<const TO: u32>(self) -> Fixed<TO> {
+        Fixed::<TO>::saturate(self.rescale::<TO>())
+    }
+
+    pub const fn from_bits(bits: i64) -> Self {
+        let () = Self::VALID_FRAC;
+        Self(bits)
+    }
+
+    pub const fn to_bits(self) -> i64 {
+        self.0
+    }
+}
+
+// Explicit overflow semantics, mirroring the integer primitives. The plain
+// operators trap on overflow in debug builds and wrap in release builds.
+impl<const FRAC: u32> Fixed<FRAC> {
+    #[inline(always)]
+    fn mul_wide(a: i64, b: i64) -> i128 {
+        ((a as i128) * (b as i128)) >> Self::DECIMAL_BITS
+    }
+
+    #[inline(always)]
+    fn div_wide(a: i64, b: i64) -> i128 {
+        ((a as i128) << Self::DECIMAL_BITS) / (b as i128)
+    }
+
+    #[inline(always)]
+    fn narrow(wide: i128) -> (Self, bool) {
+        let narrow = wide as i64;
+        (Self(narrow), narrow as i128 != wide)
+    }
+
+    #[inline(always)]
+    fn saturate(wide: i128) -> Self {
+        if wide > Self::I_MAX_VALUE as i128 {
+            Self::MAX
+        } else if wide < Self::I_MIN_VALUE as i128 {
+            Self::MIN
+        } else {
+            Self(wide as i64)
+        }
+    }
+
+    pub fn checked_new(value: i64) -> Option<Self> {
+        value.checked_mul(Self::I_ONE).map(Self)
+    }
+
+    pub fn saturating_new(value: i64) -> Self {
+        Self(value.saturating_mul(Self::I_ONE))
+    }
+
+    pub fn wrapping_new(value: i64) -> Self {
+        Self(value.wrapping_mul(Self::I_ONE))
+    }
+
+    pub fn overflowing_new(value: i64) -> (Self, bool) {
+        let (raw, overflow) = value.overflowing_mul(Self::I_ONE);
+        (Self(raw), overflow)
+    }
+
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        self.0.checked_add(rhs.0).map(Self)
+    }
+
+    pub fn saturating_add(self, rhs: Self) -> Self {
+        Self(self.0.saturating_add(rhs.0))
+    }
+
+    pub fn wrapping_add(self, rhs: Self) -> Self {
+        Self(self.0.wrapping_add(rhs.0))
+    }
+
+    pub fn overflowing_add(self, rhs: Self) -> (Self, bool) {
+        let (raw, overflow) = self.0.overflowing_add(rhs.0);
+        (Self(raw), overflow)
+    }
+
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        self.0.checked_sub(rhs.0).map(Self)
+    }
+
+    pub fn saturating_sub(self, rhs: Self) -> Self {
+        Self(self.0.saturating_sub(rhs.0))
+    }
+
+    pub fn wrapping_sub(self, rhs: Self) -> Self {
+        Self(self.0.wrapping_sub(rhs.0))
+    }
+
+    pub fn overflowing_sub(self, rhs: Self) -> (Self, bool) {
+        let (raw, overflow) = self.0.overflowing_sub(rhs.0);
+        (Self(raw), overflow)
+    }
+
+    pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+        match Self::overflowing_mul(self, rhs) {
+            (result, false) => Some(result),
+            _ => None,
+        }
+    }
+
+    pub fn saturating_mul(self, rhs: Self) -> Self {
+        Self::saturate(Self::mul_wide(self.0, rhs.0))
+    }
+
+    pub fn wrapping_mul(self, rhs: Self) -> Self {
+        Self::overflowing_mul(self, rhs).0
+    }
+
+    pub fn overflowing_mul(self, rhs: Self) -> (Self, bool) {
+        Self::narrow(Self::mul_wide(self.0, rhs.0))
+    }
+
+    // Division by zero returns None instead of panicking
+    pub fn checked_div(self, rhs: Self) -> Option<Self> {
+        if rhs.0 == 0 {
+            return None;
+        }
+        match Self::overflowing_div(self, rhs) {
+            (result, false) => Some(result),
+            _ => None,
+        }
+    }
+
+    pub fn saturating_div(self, rhs: Self) -> Self {
+        Self::saturate(Self::div_wide(self.0, rhs.0))
+    }
+
+    pub fn wrapping_div(self, rhs: Self) -> Self {
+        Self::overflowing_div(self, rhs).0
+    }
+
+    pub fn overflowing_div(self, rhs: Self) -> (Self, bool) {
+        Self::narrow(Self::div_wide(self.0, rhs.0))
+    }
+
+    pub fn checked_rem(self, rhs: Self) -> Option<Self> {
+        self.0.checked_rem(rhs.0).map(Self)
+    }
+
+    pub fn wrapping_rem(self, rhs: Self) -> Self {
+        Self(self.0.wrapping_rem(rhs.0))
+    }
+
+    pub fn overflowing_rem(self, rhs: Self) -> (Self, bool) {
+        let (raw, overflow) = self.0.overflowing_rem(rhs.0);
+        (Self(raw), overflow)
+    }
+
+    pub fn checked_neg(self) -> Option<Self> {
+        self.0.checked_neg().map(Self)
+    }
+
+    pub fn saturating_neg(self) -> Self {
+        Self(self.0.saturating_neg())
+    }
+
+    pub fn wrapping_neg(self) -> Self {
+        Self(self.0.wrapping_neg())
+    }
+
+    pub fn overflowing_neg(self) -> (Self, bool) {
+        let (raw, overflow) = self.0.overflowing_neg();
+        (Self(raw), overflow)
+    }
+
+    pub fn checked_abs(self) -> Option<Self> {
+        self.0.checked_abs().map(Self)
+    }
+
+    pub fn saturating_abs(self) -> Self {
+        Self(self.0.saturating_abs())
+    }
+
+    pub fn wrapping_abs(self) -> Self {
+        Self(self.0.wrapping_abs())
+    }
+
+    pub fn overflowing_abs(self) -> (Self, bool) {
+        let (raw, overflow) = self.0.overflowing_abs();
+        (Self(raw), overflow)
+    }
+}
+
+// Formatting works on the integer bits only, so output never depends on
+// the host float implementation.
+impl<const FRAC: u32> Fixed<FRAC> {
+    // Fewest decimal places that still parse back to exactly the same bits.
+    // Candidates are rounded half to even like decimal_string, so the
+    // string checked here is the one that gets printed.
+    fn shortest_places(magnitude: u64) -> usize {
+        let mask = (1u128 << FRAC) - 1;
+        let half = 1u128 << (FRAC - 1);
+        let fraction = (magnitude as u128) & mask;
+        let integral_odd = (magnitude >> FRAC) & 1 == 1;
+        let mut places = 0;
+        let mut scale: u128 = 1;
+        loop {
+            let scaled = fraction * scale;
+            let truncated = scaled >> FRAC;
+            let last_odd = if places == 0 { integral_odd } else { truncated & 1 == 1 };
+            let dropped = scaled & mask;
+            let round_up = dropped > half || (dropped == half && last_odd);
+            let nearest = if round_up { truncated + 1 } else { truncated };
+            let quotient = (nearest << FRAC) / scale;
+            let remainder = (nearest << FRAC) % scale;
+            let parsed = if remainder * 2 > scale || (remainder * 2 == scale && quotient & 1 == 1) {
+                quotient + 1
+            } else {
+                quotient
+            };
+            if parsed == fraction {
+                return places;
+            }
+            places += 1;
+            scale *= 10;
+        }
+    }
+
+    // Magnitude as a decimal with the given places, rounded half to even
+    fn decimal_string(magnitude: u64, places: usize) -> String {
+        let mask = (1u128 << FRAC) - 1;
+        let mut integral = (magnitude as u128) >> FRAC;
+        let mut remainder = (magnitude as u128) & mask;
+
+        let mut digits: Vec<u8> = Vec::with_capacity(places);
+        for _ in 0..places {
+            remainder *= 10;
+            digits.push((remainder >> FRAC) as u8);
+            remainder &= mask;
+        }
+
+        let half = 1u128 << (FRAC - 1);
+        let last_odd = digits.last().map_or(integral & 1 == 1, |d| d & 1 == 1);
+        if remainder > half || (remainder == half && last_odd) {
+            let mut carry = true;
+            for digit in digits.iter_mut().rev() {
+                if *digit == 9 {
+                    *digit = 0;
+                } else {
+                    *digit += 1;
+                    carry = false;
+                    break;
+                }
+            }
+            if carry {
+                integral += 1;
+            }
+        }
+
+        let mut text = integral.to_string();
+        if places > 0 {
+            text.push('.');
+            text.extend(digits.iter().map(|&d| (b'0' + d) as char));
+        }
+        text
+    }
+}
+
+// Exact decimal value. Without a precision the shortest string that parses
+// back to the same bits is printed; width, fill, alignment, sign and zero
+// padding flags behave as they do for integers.
+impl<const FRAC: u32> fmt::Display for Fixed<FRAC> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let magnitude = self.0.unsigned_abs();
+        let places = match f.precision() {
+            Some(places) => places,
+            None => Self::shortest_places(magnitude),
+        };
+        f.pad_integral(self.0 >= 0, "", &Self::decimal_string(magnitude, places))
+    }
+}
+
+// Debug shows the raw bits next to the decimal value, which makes it easy
+// to spot single bit differences between peers
+impl<const FRAC: u32> fmt::Debug for Fixed<FRAC> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Fixed<{}>({}, {:#018x})", FRAC, self, self.0)
+    }
+}
+
+impl<const FRAC: u32> fmt::LowerHex for Fixed<FRAC> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::LowerHex::fmt(&self.0, f)
+    }
+}
+
+impl<const FRAC: u32> fmt::UpperHex for Fixed<FRAC> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::UpperHex::fmt(&self.0, f)
+    }
+}
+
+impl<const FRAC: u32> fmt::Binary for Fixed<FRAC> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let value = self.0 as f64;
-        let divider = Fix::I_ONE as f64;
-        write!(f, "{}", (value / divider))
+        fmt::Binary::fmt(&self.0, f)
     }
 }
 
-impl ops::Add<Fix> for Fix {
-    type Output = Fix;
+impl<const FRAC: u32> ops::Add for Fixed<FRAC> {
+    type Output = Self;
 
     #[inline(always)]
-    fn add(self, _rhs: Fix) -> Fix {
-        Fix(self.0 + _rhs.0)
+    fn add(self, _rhs: Self) -> Self {
+        Self(self.0 + _rhs.0)
     }
 }
 
-impl ops::AddAssign<Fix> for Fix {
+impl<const FRAC: u32> ops::AddAssign for Fixed<FRAC> {
     #[inline(always)]
-    fn add_assign(&mut self, _rhs: Fix) {
+    fn add_assign(&mut self, _rhs: Self) {
         *self = *self + _rhs;
     }
 }
 
-impl ops::Sub<Fix> for Fix {
-    type Output = Fix;
+impl<const FRAC: u32> ops::Sub for Fixed<FRAC> {
+    type Output = Self;
 
     #[inline(always)]
-    fn sub(self, _rhs: Fix) -> Fix {
-        Fix(self.0 - _rhs.0)
+    fn sub(self, _rhs: Self) -> Self {
+        Self(self.0 - _rhs.0)
     }
 }
 
-impl ops::SubAssign<Fix> for Fix {
+impl<const FRAC: u32> ops::SubAssign for Fixed<FRAC> {
     #[inline(always)]
-    fn sub_assign(&mut self, _rhs: Fix) {
+    fn sub_assign(&mut self, _rhs: Self) {
         *self = *self - _rhs;
     }
 }
 
-impl ops::Neg for Fix {
-    type Output = Fix;
+impl<const FRAC: u32> ops::Neg for Fixed<FRAC> {
+    type Output = Self;
 
     #[inline(always)]
-    fn neg(self) -> Fix {
-        Fix(-self.0)
+    fn neg(self) -> Self {
+        Self(-self.0)
     }
 }
 
-impl ops::Mul<Fix> for Fix {
-    type Output = Fix;
+impl<const FRAC: u32> ops::Mul for Fixed<FRAC> {
+    type Output = Self;
 
     #[inline(always)]
-    fn mul(self, _rhs: Fix) -> Fix {
-        Fix(((self.0 as i128) * (_rhs.0 as i128) >> Fix::DECIMAL_BITS) as i64)
+    fn mul(self, _rhs: Self) -> Self {
+        if cfg!(debug_assertions) {
+            self.checked_mul(_rhs).expect("attempt to multiply with overflow")
+        } else {
+            self.wrapping_mul(_rhs)
+        }
     }
 }
 
-impl ops::MulAssign<Fix> for Fix {
+impl<const FRAC: u32> ops::MulAssign for Fixed<FRAC> {
     #[inline(always)]
-    fn mul_assign(&mut self, _rhs: Fix) {
+    fn mul_assign(&mut self, _rhs: Self) {
         *self = *self * _rhs;
     }
 }
 
 
-impl ops::Div<Fix> for Fix {
-    type Output = Fix;
+impl<const FRAC: u32> ops::Div for Fixed<FRAC> {
+    type Output = Self;
 
     #[inline(always)]
-    fn div(self, _rhs: Fix) -> Fix {
-        Fix((((self.0 as i128) << Fix::DECIMAL_BITS) / (_rhs.0 as i128)) as i64)
+    fn div(self, _rhs: Self) -> Self {
+        if cfg!(debug_assertions) {
+            let (result, overflow) = self.overflowing_div(_rhs);
+            if overflow {
+                panic!("attempt to divide with overflow");
+            }
+            result
+        } else {
+            self.wrapping_div(_rhs)
+        }
     }
 }
 
-impl ops::DivAssign<Fix> for Fix {
+impl<const FRAC: u32> ops::DivAssign for Fixed<FRAC> {
     #[inline(always)]
-    fn div_assign(&mut self, _rhs: Fix) {
+    fn div_assign(&mut self, _rhs: Self) {
         *self = *self / _rhs;
     }
 }
 
-impl ops::Rem<Fix> for Fix {
-    type Output = Fix;
+impl<const FRAC: u32> ops::Rem for Fixed<FRAC> {
+    type Output = Self;
 
     #[inline(always)]
-    fn rem(self, _rhs: Fix) -> Fix {
-        Fix(self.0 % _rhs.0)
+    fn rem(self, _rhs: Self) -> Self {
+        Self(self.0 % _rhs.0)
     }
 }
 
-impl From<Fix> for f64 {
-    fn from(value: Fix) -> Self {
-        (value.0 as f64) / (Fix::I_ONE as f64)
+impl<const FRAC: u32> From<Fixed<FRAC>> for f64 {
+    fn from(value: Fixed<FRAC>) -> Self {
+        (value.0 as f64) / (Fixed::<FRAC>::I_ONE as f64)
     }
 }
 
-impl From<Fix> for f32 {
-    fn from(value: Fix) -> Self {
-        (value.0 as f32) / (Fix::I_ONE as f32)
+impl<const FRAC: u32> From<Fixed<FRAC>> for f32 {
+    fn from(value: Fixed<FRAC>) -> Self {
+        (value.0 as f32) / (Fixed::<FRAC>::I_ONE as f32)
     }
 }
 
-impl From<Fix> for i32 {
-    fn from(value: Fix) -> Self {
-        (value.0 as i32) / (Fix::I_ONE as i32)
+// Truncates toward zero like the i64 conversion, failing when the integer
+// part does not fit
+impl<const FRAC: u32> TryFrom<Fixed<FRAC>> for i32 {
+    type Error = TryFromIntError;
+
+    fn try_from(value: Fixed<FRAC>) -> Result<Self, Self::Error> {
+        i32::try_from(i64::from(value))
     }
 }
 
-impl From<Fix> for i64 {
-    fn from(value: Fix) -> Self {
-        value.0 / Fix::I_ONE
+impl<const FRAC: u32> From<Fixed<FRAC>> for i64 {
+    fn from(value: Fixed<FRAC>) -> Self {
+        value.0 / Fixed::<FRAC>::I_ONE
     }
 }
 
-impl From<f64> for Fix {
+impl<const FRAC: u32> From<f64> for Fixed<FRAC> {
     fn from(value: f64) -> Self {
-        Fix((value * (Self::I_ONE as f64)) as i64)
+        Self((value * (Self::I_ONE as f64)) as i64)
     }
 }
 
-impl From<f32> for Fix {
+impl<const FRAC: u32> From<f32> for Fixed<FRAC> {
     fn from(value: f32) -> Self {
-        Fix((value * (Self::I_ONE as f32)) as i64)
+        Self((value * (Self::I_ONE as f32)) as i64)
     }
 }
 
-impl From<i32> for Fix {
+impl<const FRAC: u32> From<i32> for Fixed<FRAC> {
     fn from(value: i32) -> Self {
-        Fix((value * (Self::I_ONE as i32)) as i64)
+        Self::new(i64::from(value))
     }
 }
 
-impl From<i64> for Fix {
+impl<const FRAC: u32> From<i64> for Fixed<FRAC> {
     fn from(value: i64) -> Self {
-        Fix(value * Self::I_ONE)
+        Self::new(value)
     }
 }
 
 pub fn testfun() {
-    let tn = Fix::MAX + Fix::new(2);
-    let tn2 = Fix::MAX + Fix::new(2);
-    let tr = tn == Fix::new(-4294967294);
+    let tn = Fix::MAX.wrapping_add(Fix::new(2));
+    let tn2 = Fix::MAX.saturating_add(Fix::new(2));
+    let tr = Fix::MAX.checked_add(Fix::new(2)).is_none();
     println!("Hello {} {} {} {}", tn, tr, tn.0, tn2.0);
     println!("Sqrt {} {} {} {}", Fix::sqrt(Fix::new(2)), Fix::sqrt(Fix::new(10)), Fix::sqrt(Fix::new(100)), Fix::sqrt(Fix::new(1337)));
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The documented sine error bound has to hold far from zero too, where
+    // wrapping with a 2π rounded to the format used to lose it
+    // Angles of every magnitude with at most 26 significant bits, so they
+    // are exact as f64
+    fn sample_angles() -> Vec<i64> {
+        let mut seed = 0x2545_f491_4f6c_dd1d_u64;
+        let mut angles = vec![i64::MIN, Fix::from(8_000_000_000i64).to_bits()];
+        for shift in 0..38 {
+            for _ in 0..16 {
+                seed ^= seed << 13;
+                seed ^= seed >> 7;
+                seed ^= seed << 17;
+                let bits = (((seed >> 38) | (1 << 25)) as i64) << shift;
+                angles.push(if seed & 1 == 0 { bits } else { -bits });
+            }
+        }
+        angles
+    }
+
+    #[test]
+    fn sin_cos_tan_error_bound_holds_at_large_arguments() {
+        // Interpolation error of the table, CORDIC stays within rounding
+        #[cfg(feature = "lut-sin")]
+        let bound = {
+            let step = std::f64::consts::FRAC_PI_2 / Fix::LUT_SIZE as f64;
+            step * step / 8.0 + 2f64.powi(-30)
+        };
+        #[cfg(not(feature = "lut-sin"))]
+        let bound = 2f64.powi(-30);
+        let rounding = 2f64.powi(-31);
+
+        for bits in sample_angles() {
+            let angle = Fix::from_bits(bits);
+            let x = bits as f64 / (1u64 << 30) as f64;
+            let (sin, cos) = angle.sin_cos();
+            assert!((f64::from(angle.sin()) - x.sin()).abs() <= bound + rounding, "sin({})", x);
+            assert!((f64::from(angle.cos()) - x.cos()).abs() <= bound + rounding, "cos({})", x);
+            assert_eq!((sin, cos), (angle.sin(), angle.cos()));
+
+            let tan = x.tan();
+            if tan.abs() < 1e6 {
+                let error = (f64::from(angle.tan()) - tan).abs();
+                assert!(error <= bound * (1.0 + tan * tan) * 1.01 + rounding, "tan({})", x);
+            }
+        }
+    }
+
+    #[cfg(feature = "lut-tan")]
+    #[test]
+    fn tan_fast_error_bound_holds_at_large_arguments() {
+        let step = std::f64::consts::FRAC_PI_2 / Fix::LUT_SIZE as f64;
+        let rounding = 2f64.powi(-31);
+        for bits in sample_angles() {
+            let angle = Fix::from_bits(bits);
+            let x = bits as f64 / (1u64 << 30) as f64;
+            let tan = x.tan();
+            let fast = angle.tan_fast();
+            if fast == Fix::MAX || fast == Fix::MIN {
+                assert!(tan.abs() > 1.9 / step, "tan_fast({})", x);
+                continue;
+            }
+            let error = (f64::from(fast) - tan).abs();
+            assert!(error <= step * (1.0 + tan * tan) + 2.0 * rounding, "tan_fast({})", x);
+            if bits != i64::MIN {
+                assert_eq!(fast, -(-angle).tan_fast());
+            }
+        }
+    }
+
+    #[test]
+    fn checked_saturating_wrapping_and_overflowing_at_the_limits() {
+        let (max, min, one) = (Fix::MAX, Fix::MIN, Fix::ONE);
+        let epsilon = Fix::from_bits(1);
+
+        assert_eq!(max.checked_add(epsilon), None);
+        assert_eq!(max.checked_add(-epsilon), Some(Fix::from_bits(i64::MAX - 1)));
+        assert_eq!(max.saturating_add(one), max);
+        assert_eq!(min.saturating_add(-one), min);
+        assert_eq!(max.wrapping_add(epsilon), min);
+        assert_eq!(max.overflowing_add(epsilon), (min, true));
+        assert_eq!(max.overflowing_add(Fix::ZERO), (max, false));
+
+        assert_eq!(min.checked_sub(epsilon), None);
+        assert_eq!(min.saturating_sub(one), min);
+        assert_eq!(max.saturating_sub(-one), max);
+        assert_eq!(min.wrapping_sub(epsilon), max);
+        assert_eq!(min.overflowing_sub(epsilon), (max, true));
+
+        assert_eq!(max.checked_mul(Fix::TWO), None);
+        assert_eq!(max.checked_mul(one), Some(max));
+        assert_eq!(max.saturating_mul(Fix::TWO), max);
+        assert_eq!(max.saturating_mul(-Fix::TWO), min);
+        assert_eq!(min.saturating_mul(-one), max);
+        assert_eq!(max.wrapping_mul(Fix::TWO), Fix::from_bits(-2));
+        assert_eq!(max.overflowing_mul(Fix::TWO), (Fix::from_bits(-2), true));
+        assert_eq!(min.overflowing_mul(one), (min, false));
+
+        assert_eq!(max.checked_div(Fix::HALF), None);
+        assert_eq!(max.checked_div(Fix::ZERO), None);
+        assert_eq!(max.checked_div(one), Some(max));
+        assert_eq!(max.saturating_div(Fix::HALF), max);
+        assert_eq!(min.saturating_div(Fix::HALF), min);
+        assert_eq!(min.saturating_div(-one), max);
+        assert_eq!(min.wrapping_div(-one), min);
+        assert_eq!(min.overflowing_div(-one), (min, true));
+        assert_eq!(max.overflowing_div(Fix::HALF), (Fix::from_bits(-2), true));
+
+        assert_eq!(min.checked_rem(-epsilon), None);
+        assert_eq!(max.checked_rem(Fix::ZERO), None);
+        assert_eq!(min.wrapping_rem(-epsilon), Fix::ZERO);
+        assert_eq!(min.overflowing_rem(-epsilon), (Fix::ZERO, true));
+
+        assert_eq!(min.checked_neg(), None);
+        assert_eq!(max.checked_neg(), Some(Fix::from_bits(-i64::MAX)));
+        assert_eq!(min.saturating_neg(), max);
+        assert_eq!(min.wrapping_neg(), min);
+        assert_eq!(min.overflowing_neg(), (min, true));
+
+        assert_eq!(min.checked_abs(), None);
+        assert_eq!(min.saturating_abs(), max);
+        assert_eq!(min.wrapping_abs(), min);
+        assert_eq!(min.overflowing_abs(), (min, true));
+        assert_eq!(max.overflowing_abs(), (max, false));
+
+        assert_eq!(Fix::checked_new(1 << 33), None);
+        assert_eq!(Fix::checked_new(-(1 << 33)), Some(min));
+        assert_eq!(Fix::saturating_new(i64::MAX), max);
+        assert_eq!(Fix::saturating_new(i64::MIN), min);
+        assert_eq!(Fix::wrapping_new(1 << 33), min);
+        assert_eq!(Fix::overflowing_new(1 << 33), (min, true));
+        assert_eq!(Fix::overflowing_new(-1), (-one, false));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "attempt to multiply with overflow")]
+    fn mul_traps_on_overflow_in_debug() {
+        let _ = Fix::MAX * Fix::TWO;
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "attempt to divide with overflow")]
+    fn div_traps_on_overflow_in_debug() {
+        let _ = Fix::MAX / Fix::HALF;
+    }
+
+    #[test]
+    #[cfg(not(debug_assertions))]
+    fn mul_and_div_wrap_on_overflow_in_release() {
+        assert_eq!(Fix::MAX * Fix::TWO, Fix::MAX.wrapping_mul(Fix::TWO));
+        assert_eq!(Fix::MAX / Fix::HALF, Fix::MAX.wrapping_div(Fix::HALF));
+    }
+
+    #[test]
+    fn i32_conversion_fails_instead_of_truncating() {
+        assert_eq!(i32::try_from(Fix::new(-7) - Fix::HALF), Ok(-7));
+        assert_eq!(i32::try_from(Fix::new(i32::MAX as i64)), Ok(i32::MAX));
+        assert!(i32::try_from(Fix::new(1 << 31)).is_err());
+        assert!(i32::try_from(Fix::MIN).is_err());
+    }
+
+    #[test]
+    fn formats_at_both_ends_of_the_range_work() {
+        type Coarse = Fixed<1>;
+        type Fine = Fixed<56>;
+        assert_eq!(Coarse::new(3) * Coarse::HALF, Coarse::from_bits(3));
+        assert_eq!(Coarse::PI, Coarse::new(3));
+        assert_eq!(Fine::ONE.to_bits(), 1 << 56);
+        assert_eq!(Fine::new(2) * Fine::HALF, Fine::ONE);
+        assert_eq!(Fine::RAD_TO_DEG.to_bits() >> 56, 57);
+        assert_eq!(Fine::from_bits(7).to_bits(), 7);
+    }
+
+    // Shortest output parses back to the same bits, and one place fewer
+    // would not
+    fn assert_display_round_trips<const FRAC: u32>(value: Fixed<FRAC>) {
+        let text = value.to_string();
+        assert_eq!(text.parse::<Fixed<FRAC>>(), Ok(value), "{}", text);
+        let places = text.split('.').nth(1).map_or(0, str::len);
+        if places > 0 {
+            let shorter = format!("{:.*}", places - 1, value);
+            assert_ne!(shorter.parse::<Fixed<FRAC>>(), Ok(value), "{}", shorter);
+        }
+    }
+
+    fn round_trip_samples<const FRAC: u32>() {
+        let mut state: u64 = 0x9e37_79b9_7f4a_7c15;
+        let mut samples = vec![i64::MIN, i64::MIN + 1, -1, 0, 1, i64::MAX, 1 << (FRAC - 1), 3 << (FRAC - 1)];
+        for _ in 0..2000 {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            samples.push(state as i64 >> (state >> 58));
+        }
+        for bits in samples {
+            assert_display_round_trips(Fixed::<FRAC>::from_bits(bits));
+        }
+    }
+
+    #[test]
+    fn display_parses_back_to_the_same_bits() {
+        round_trip_samples::<1>();
+        round_trip_samples::<4>();
+        round_trip_samples::<16>();
+        round_trip_samples::<30>();
+        round_trip_samples::<32>();
+        round_trip_samples::<56>();
+        assert_eq!(Fix::HALF.to_string(), "0.5");
+        assert_eq!(Fixed::<1>::from_bits(3).to_string(), "1.5");
+        assert_eq!(format!("{:.0}", Fixed::<1>::from_bits(5)), "2");
+        assert_eq!(format!("{:+08.2}", -Fix::ONE), "-0001.00");
+    }
+}
@@ -0,0 +1,629 @@
+use std::convert::TryFrom;
+use std::fmt;
+use std::ops;
+
+use super::fix::{Fix, Q48_16};
+
+// Q16.16 companion of Fix for compact storage. Arithmetic widens to i64
+// at most, and every value converts to Fix without loss.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub struct Fix32(i32);
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct TryFromFixError(());
+
+impl fmt::Display for TryFromFixError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "value is not exactly representable as Fix32")
+    }
+}
+
+impl std::error::Error for TryFromFixError {}
+
+impl Fix32 {
+    pub fn new(value: i32) -> Fix32 {
+        Fix32(value * Self::I_ONE)
+    }
+
+    // Rounds to the nearest Fix32 and saturates out of range values
+    pub fn from_fix(value: Fix) -> Fix32 {
+        Fix32(value.convert::<16>().to_bits().clamp(i32::MIN as i64, i32::MAX as i64) as i32)
+    }
+
+    pub fn to_fix(self) -> Fix {
+        Fix::from(self)
+    }
+
+    pub const fn from_bits(bits: i32) -> Fix32 {
+        Fix32(bits)
+    }
+
+    pub const fn to_bits(self) -> i32 {
+        self.0
+    }
+
+    const DECIMAL_BITS: i32 = 16;
+    const BITS: i32 = 32;
+    const DECIMAL_MASK: i32 = (u32::MAX >> (Self::BITS - Self::DECIMAL_BITS)) as i32;
+
+    const I_ONE: i32 = 1 << Self::DECIMAL_BITS;
+    const I_HALF: i32 = 1 << (Self::DECIMAL_BITS - 1);
+
+    pub const ZERO: Fix32 = Fix32(0);
+    pub const ONE: Fix32 = Fix32(Self::I_ONE);
+    pub const TWO: Fix32 = Fix32(Self::I_ONE * 2);
+    pub const HALF: Fix32 = Fix32(Self::I_HALF);
+    pub const TEN: Fix32 = Fix32(Self::I_ONE * 10);
+
+    pub const MAX: Fix32 = Fix32(i32::MAX);
+    pub const MIN: Fix32 = Fix32(i32::MIN);
+
+    pub const PI: Fix32 = Fix32(Q48_16::PI.to_bits() as i32);
+    pub const PI_TIMES_TWO: Fix32 = Fix32(Q48_16::PI_TIMES_TWO.to_bits() as i32);
+    pub const PI_OVER_TWO: Fix32 = Fix32(Q48_16::PI_OVER_TWO.to_bits() as i32);
+    pub const PI_INVERTED: Fix32 = Fix32(Q48_16::PI_INVERTED.to_bits() as i32);
+    pub const PI_OVER_TWO_INVERTED: Fix32 = Fix32(Q48_16::PI_OVER_TWO_INVERTED.to_bits() as i32);
+
+    pub const DEG_TO_RAD: Fix32 = Fix32(Q48_16::DEG_TO_RAD.to_bits() as i32);
+    pub const RAD_TO_DEG: Fix32 = Fix32(Q48_16::RAD_TO_DEG.to_bits() as i32);
+
+    const ATAN2_HELP: Fix32 = Fix32(18350); // 0.28
+
+    pub fn sign(value: Fix32) -> Fix32 {
+        if value.0 < 0 {
+            -Self::ONE
+        } else if value.0 > 0 {
+            Self::ONE
+        } else {
+            Self::ZERO
+        }
+    }
+
+    pub fn abs(value: Fix32) -> Fix32 {
+        let mask = value.0 >> (Self::BITS - 1);
+        Fix32((value.0 + mask) ^ mask)
+    }
+
+    pub fn to_radians(self) -> Fix32 {
+        self * Self::DEG_TO_RAD
+    }
+
+    pub fn to_degrees(self) -> Fix32 {
+        self * Self::RAD_TO_DEG
+    }
+
+    pub fn floor(value: Fix32) -> Fix32 {
+        Fix32(value.0 & (!Self::DECIMAL_MASK))
+    }
+
+    pub fn ceiling(value: Fix32) -> Fix32 {
+        let has_frac = (value.0 & Self::DECIMAL_MASK) != 0;
+        if has_frac { Self::floor(value) + Self::ONE } else { value }
+    }
+
+    pub fn round(value: Fix32) -> Fix32 {
+        let fract = value.0 & Self::DECIMAL_MASK;
+        let integral = Self::floor(value);
+        if fract < Self::I_HALF {
+            return integral;
+        }
+        // Halves are always rounded upwards
+        integral + Self::ONE
+    }
+
+    pub fn sqrt(value: Fix32) -> Fix32 {
+        let xl = value.0;
+        if xl < 0 {
+            panic!("Sqrt for negative number");
+        }
+
+        let mut num = (xl as u64) << Self::DECIMAL_BITS;
+        let mut result: u64 = 0;
+
+        let mut bit: u64 = 1 << 62;
+
+        while bit > num {
+            bit >>= 2;
+        }
+
+        while bit != 0 {
+            if num >= result + bit {
+                num -= result + bit;
+                result = (result >> 1) + bit;
+            }
+            else {
+                result >>= 1;
+            }
+            bit >>= 2;
+        }
+
+        if num > result {
+            result += 1;
+        }
+
+        Fix32(result as i32)
+    }
+
+    // Same CORDIC evaluation as Fix::atan2, on the i64 format with the
+    // same fractional bits
+    pub fn atan2(y: Fix32, x: Fix32) -> Fix32 {
+        Self::from_q48_16(Q48_16::atan2(y.to_q48_16(), x.to_q48_16()))
+    }
+
+    pub fn atan2_fast(y: Fix32, x: Fix32) -> Fix32 {
+        // Approximate atan2 with error < 0.005
+        let yl = y.0;
+        let xl = x.0;
+
+        if xl == 0 {
+            if yl > 0 {
+                return Self::PI_OVER_TWO;
+            }
+            if yl == 0 {
+                return Self::ZERO;
+            }
+            return -Self::PI_OVER_TWO;
+        }
+
+        let atan: Fix32;
+        let z = y / x;
+
+        if Fix32::abs(z) < Fix32::ONE {
+            atan = z / (Fix32::ONE + (Fix32::ATAN2_HELP * z * z));
+            if xl < 0 {
+                if yl < 0 {
+                    return atan - Self::PI;
+                }
+                return atan + Self::PI;
+            }
+        } else {
+            atan = Self::PI_OVER_TWO - (z / (z * z + Self::ATAN2_HELP));
+            if yl < 0 {
+                return atan - Self::PI;
+            }
+        }
+        atan
+    }
+
+    fn from_q48_16(value: Q48_16) -> Fix32 {
+        Fix32(value.to_bits() as i32)
+    }
+
+    fn to_q48_16(self) -> Q48_16 {
+        Q48_16::from_bits(self.0 as i64)
+    }
+
+    // The interpolated functions run on the i64 format with the same
+    // fractional bits, see Fix for their error bounds
+    pub fn sin(&self) -> Fix32 {
+        Self::from_q48_16(self.to_q48_16().sin())
+    }
+
+    pub fn cos(&self) -> Fix32 {
+        Self::from_q48_16(self.to_q48_16().cos())
+    }
+
+    pub fn sin_cos(&self) -> (Fix32, Fix32) {
+        let (sin, cos) = self.to_q48_16().sin_cos();
+        (Self::from_q48_16(sin), Self::from_q48_16(cos))
+    }
+
+    pub fn tan(&self) -> Fix32 {
+        let tan = self.to_q48_16().tan().to_bits();
+        Fix32(tan.clamp(i32::MIN as i64, i32::MAX as i64) as i32)
+    }
+
+    // Nearest table entry through the i64 format, which mirrors the table
+    // for the falling quarters and wraps the angle exactly. Without the
+    // table these fall back to the accurate functions.
+    pub fn sin_fast(&self) -> Fix32 {
+        Self::from_q48_16(self.to_q48_16().sin_fast())
+    }
+
+    pub fn cos_fast(&self) -> Fix32 {
+        Self::from_q48_16(self.to_q48_16().cos_fast())
+    }
+
+    pub fn tan_fast(&self) -> Fix32 {
+        let tan = self.to_q48_16().tan_fast().to_bits();
+        Fix32(tan.clamp(i32::MIN as i64, i32::MAX as i64) as i32)
+    }
+}
+
+// Explicit overflow semantics, mirroring Fix
+impl Fix32 {
+    #[inline(always)]
+    fn narrow(wide: i64) -> (Fix32, bool) {
+        let narrow = wide as i32;
+        (Fix32(narrow), narrow as i64 != wide)
+    }
+
+    #[inline(always)]
+    fn saturate(wide: i64) -> Fix32 {
+        Fix32(wide.clamp(i32::MIN as i64, i32::MAX as i64) as i32)
+    }
+
+    #[inline(always)]
+    fn mul_wide(a: i32, b: i32) -> i64 {
+        ((a as i64) * (b as i64)) >> Self::DECIMAL_BITS
+    }
+
+    #[inline(always)]
+    fn div_wide(a: i32, b: i32) -> i64 {
+        ((a as i64) << Self::DECIMAL_BITS) / (b as i64)
+    }
+
+    pub fn checked_new(value: i32) -> Option<Fix32> {
+        value.checked_mul(Self::I_ONE).map(Fix32)
+    }
+
+    pub fn saturating_new(value: i32) -> Fix32 {
+        Fix32(value.saturating_mul(Self::I_ONE))
+    }
+
+    pub fn wrapping_new(value: i32) -> Fix32 {
+        Fix32(value.wrapping_mul(Self::I_ONE))
+    }
+
+    pub fn overflowing_new(value: i32) -> (Fix32, bool) {
+        let (raw, overflow) = value.overflowing_mul(Self::I_ONE);
+        (Fix32(raw), overflow)
+    }
+
+    pub fn checked_add(self, rhs: Fix32) -> Option<Fix32> {
+        self.0.checked_add(rhs.0).map(Fix32)
+    }
+
+    pub fn saturating_add(self, rhs: Fix32) -> Fix32 {
+        Fix32(self.0.saturating_add(rhs.0))
+    }
+
+    pub fn wrapping_add(self, rhs: Fix32) -> Fix32 {
+        Fix32(self.0.wrapping_add(rhs.0))
+    }
+
+    pub fn overflowing_add(self, rhs: Fix32) -> (Fix32, bool) {
+        let (raw, overflow) = self.0.overflowing_add(rhs.0);
+        (Fix32(raw), overflow)
+    }
+
+    pub fn checked_sub(self, rhs: Fix32) -> Option<Fix32> {
+        self.0.checked_sub(rhs.0).map(Fix32)
+    }
+
+    pub fn saturating_sub(self, rhs: Fix32) -> Fix32 {
+        Fix32(self.0.saturating_sub(rhs.0))
+    }
+
+    pub fn wrapping_sub(self, rhs: Fix32) -> Fix32 {
+        Fix32(self.0.wrapping_sub(rhs.0))
+    }
+
+    pub fn overflowing_sub(self, rhs: Fix32) -> (Fix32, bool) {
+        let (raw, overflow) = self.0.overflowing_sub(rhs.0);
+        (Fix32(raw), overflow)
+    }
+
+    pub fn checked_mul(self, rhs: Fix32) -> Option<Fix32> {
+        match self.overflowing_mul(rhs) {
+            (result, false) => Some(result),
+            _ => None,
+        }
+    }
+
+    pub fn saturating_mul(self, rhs: Fix32) -> Fix32 {
+        Self::saturate(Self::mul_wide(self.0, rhs.0))
+    }
+
+    pub fn wrapping_mul(self, rhs: Fix32) -> Fix32 {
+        self.overflowing_mul(rhs).0
+    }
+
+    pub fn overflowing_mul(self, rhs: Fix32) -> (Fix32, bool) {
+        Self::narrow(Self::mul_wide(self.0, rhs.0))
+    }
+
+    // Division by zero returns None instead of panicking
+    pub fn checked_div(self, rhs: Fix32) -> Option<Fix32> {
+        if rhs.0 == 0 {
+            return None;
+        }
+        match self.overflowing_div(rhs) {
+            (result, false) => Some(result),
+            _ => None,
+        }
+    }
+
+    pub fn saturating_div(self, rhs: Fix32) -> Fix32 {
+        Self::saturate(Self::div_wide(self.0, rhs.0))
+    }
+
+    pub fn wrapping_div(self, rhs: Fix32) -> Fix32 {
+        self.overflowing_div(rhs).0
+    }
+
+    pub fn overflowing_div(self, rhs: Fix32) -> (Fix32, bool) {
+        Self::narrow(Self::div_wide(self.0, rhs.0))
+    }
+
+    pub fn checked_rem(self, rhs: Fix32) -> Option<Fix32> {
+        self.0.checked_rem(rhs.0).map(Fix32)
+    }
+
+    pub fn wrapping_rem(self, rhs: Fix32) -> Fix32 {
+        Fix32(self.0.wrapping_rem(rhs.0))
+    }
+
+    pub fn overflowing_rem(self, rhs: Fix32) -> (Fix32, bool) {
+        let (raw, overflow) = self.0.overflowing_rem(rhs.0);
+        (Fix32(raw), overflow)
+    }
+
+    pub fn checked_neg(self) -> Option<Fix32> {
+        self.0.checked_neg().map(Fix32)
+    }
+
+    pub fn saturating_neg(self) -> Fix32 {
+        Fix32(self.0.saturating_neg())
+    }
+
+    pub fn wrapping_neg(self) -> Fix32 {
+        Fix32(self.0.wrapping_neg())
+    }
+
+    pub fn overflowing_neg(self) -> (Fix32, bool) {
+        let (raw, overflow) = self.0.overflowing_neg();
+        (Fix32(raw), overflow)
+    }
+
+    pub fn checked_abs(self) -> Option<Fix32> {
+        self.0.checked_abs().map(Fix32)
+    }
+
+    pub fn saturating_abs(self) -> Fix32 {
+        Fix32(self.0.saturating_abs())
+    }
+
+    pub fn wrapping_abs(self) -> Fix32 {
+        Fix32(self.0.wrapping_abs())
+    }
+
+    pub fn overflowing_abs(self) -> (Fix32, bool) {
+        let (raw, overflow) = self.0.overflowing_abs();
+        (Fix32(raw), overflow)
+    }
+}
+
+// Formatted through the i64 type with the same fraction, so the shortest
+// round-trip output matches Q16.16 precision
+impl fmt::Display for Fix32 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&Q48_16::from_bits(self.0 as i64), f)
+    }
+}
+
+impl fmt::Debug for Fix32 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Fix32({}, {:#010x})", self, self.0)
+    }
+}
+
+impl fmt::LowerHex for Fix32 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::LowerHex::fmt(&self.0, f)
+    }
+}
+
+impl fmt::UpperHex for Fix32 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::UpperHex::fmt(&self.0, f)
+    }
+}
+
+impl fmt::Binary for Fix32 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Binary::fmt(&self.0, f)
+    }
+}
+
+impl ops::Add<Fix32> for Fix32 {
+    type Output = Fix32;
+
+    #[inline(always)]
+    fn add(self, _rhs: Fix32) -> Fix32 {
+        Fix32(self.0 + _rhs.0)
+    }
+}
+
+impl ops::AddAssign<Fix32> for Fix32 {
+    #[inline(always)]
+    fn add_assign(&mut self, _rhs: Fix32) {
+        *self = *self + _rhs;
+    }
+}
+
+impl ops::Sub<Fix32> for Fix32 {
+    type Output = Fix32;
+
+    #[inline(always)]
+    fn sub(self, _rhs: Fix32) -> Fix32 {
+        Fix32(self.0 - _rhs.0)
+    }
+}
+
+impl ops::SubAssign<Fix32> for Fix32 {
+    #[inline(always)]
+    fn sub_assign(&mut self, _rhs: Fix32) {
+        *self = *self - _rhs;
+    }
+}
+
+impl ops::Neg for Fix32 {
+    type Output = Fix32;
+
+    #[inline(always)]
+    fn neg(self) -> Fix32 {
+        Fix32(-self.0)
+    }
+}
+
+impl ops::Mul<Fix32> for Fix32 {
+    type Output = Fix32;
+
+    #[inline(always)]
+    fn mul(self, _rhs: Fix32) -> Fix32 {
+        if cfg!(debug_assertions) {
+            self.checked_mul(_rhs).expect("attempt to multiply with overflow")
+        } else {
+            self.wrapping_mul(_rhs)
+        }
+    }
+}
+
+impl ops::MulAssign<Fix32> for Fix32 {
+    #[inline(always)]
+    fn mul_assign(&mut self, _rhs: Fix32) {
+        *self = *self * _rhs;
+    }
+}
+
+impl ops::Div<Fix32> for Fix32 {
+    type Output = Fix32;
+
+    #[inline(always)]
+    fn div(self, _rhs: Fix32) -> Fix32 {
+        if cfg!(debug_assertions) {
+            let (result, overflow) = self.overflowing_div(_rhs);
+            if overflow {
+                panic!("attempt to divide with overflow");
+            }
+            result
+        } else {
+            self.wrapping_div(_rhs)
+        }
+    }
+}
+
+impl ops::DivAssign<Fix32> for Fix32 {
+    #[inline(always)]
+    fn div_assign(&mut self, _rhs: Fix32) {
+        *self = *self / _rhs;
+    }
+}
+
+impl ops::Rem<Fix32> for Fix32 {
+    type Output = Fix32;
+
+    #[inline(always)]
+    fn rem(self, _rhs: Fix32) -> Fix32 {
+        Fix32(self.0 % _rhs.0)
+    }
+}
+
+impl From<Fix32> for Fix {
+    fn from(value: Fix32) -> Self {
+        Fix::from_bits((value.0 as i64) << (Fix::DECIMAL_BITS - Fix32::DECIMAL_BITS as i64))
+    }
+}
+
+// Succeeds only when no precision or range is lost
+impl TryFrom<Fix> for Fix32 {
+    type Error = TryFromFixError;
+
+    fn try_from(value: Fix) -> Result<Self, Self::Error> {
+        value.convert_exact::<16>()
+            .and_then(|v| i32::try_from(v.to_bits()).ok())
+            .map(Fix32)
+            .ok_or(TryFromFixError(()))
+    }
+}
+
+impl From<Fix32> for f64 {
+    fn from(value: Fix32) -> Self {
+        (value.0 as f64) / (Fix32::I_ONE as f64)
+    }
+}
+
+impl From<Fix32> for f32 {
+    fn from(value: Fix32) -> Self {
+        (value.0 as f32) / (Fix32::I_ONE as f32)
+    }
+}
+
+impl From<Fix32> for i32 {
+    fn from(value: Fix32) -> Self {
+        value.0 / Fix32::I_ONE
+    }
+}
+
+impl From<f64> for Fix32 {
+    fn from(value: f64) -> Self {
+        Fix32((value * (Self::I_ONE as f64)) as i32)
+    }
+}
+
+impl From<f32> for Fix32 {
+    fn from(value: f32) -> Self {
+        Fix32((value * (Self::I_ONE as f32)) as i32)
+    }
+}
+
+impl From<i32> for Fix32 {
+    fn from(value: i32) -> Self {
+        Fix32::new(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_and_unary_families_at_the_limits() {
+        let (max, min) = (Fix32::MAX, Fix32::MIN);
+        assert_eq!(Fix32::checked_new(1 << 15), None);
+        assert_eq!(Fix32::checked_new(-(1 << 15)), Some(min));
+        assert_eq!(Fix32::saturating_new(i32::MAX), max);
+        assert_eq!(Fix32::saturating_new(i32::MIN), min);
+        assert_eq!(Fix32::wrapping_new(1 << 15), min);
+        assert_eq!(Fix32::overflowing_new(1 << 15), (min, true));
+        assert_eq!(Fix32::overflowing_new(-3), (Fix32::new(-3), false));
+
+        assert_eq!(min.checked_rem(Fix32::from_bits(-1)), None);
+        assert_eq!(min.wrapping_rem(Fix32::from_bits(-1)), Fix32::ZERO);
+        assert_eq!(min.overflowing_rem(Fix32::from_bits(-1)), (Fix32::ZERO, true));
+
+        assert_eq!(min.overflowing_neg(), (min, true));
+        assert_eq!(max.overflowing_neg(), (Fix32::from_bits(-i32::MAX), false));
+        assert_eq!(min.checked_abs(), None);
+        assert_eq!(min.saturating_abs(), max);
+        assert_eq!(min.wrapping_abs(), min);
+        assert_eq!(min.overflowing_abs(), (min, true));
+        assert_eq!((-Fix32::ONE).overflowing_abs(), (Fix32::ONE, false));
+    }
+
+    #[test]
+    fn converts_to_fix_exactly() {
+        for bits in [i32::MIN, -65537, -1, 0, 1, 0x1234_5678, i32::MAX] {
+            let value = Fix32::from_bits(bits);
+            assert_eq!(f64::from(Fix::from(value)), f64::from(value));
+            assert_eq!(Fix32::try_from(Fix::from(value)), Ok(value));
+        }
+    }
+
+    // Mirroring the falling quarters must not shift them by an entry
+    #[test]
+    fn sin_fast_matches_the_wide_format_and_stays_symmetric() {
+        for bits in (-(6 << 16)..(6 << 16)).step_by(97) {
+            let angle = Fix32::from_bits(bits);
+            let wide = Q48_16::from_bits(bits as i64);
+            assert_eq!(angle.sin_fast().to_bits() as i64, wide.sin_fast().to_bits());
+            assert_eq!(angle.cos_fast().to_bits() as i64, wide.cos_fast().to_bits());
+            assert!((f64::from(angle.sin_fast()) - f64::from(angle).sin()).abs() < 8e-4);
+
+            let mirrored = Fix32::PI - angle;
+            let difference = f64::from(mirrored.sin_fast()) - f64::from(angle.sin_fast());
+            assert!(difference.abs() < 1e-3, "sin_fast({})", angle);
+        }
+    }
+}
@@ -0,0 +1,421 @@
+use std::cmp::Ordering;
+
+use super::fix::Fix;
+use super::vec2::Vec2;
+use super::wide::I256;
+
+// Which side of a directed line a point lies on
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Orientation {
+    Clockwise,
+    Collinear,
+    CounterClockwise,
+}
+
+// Exact orientation of the turn a -> b -> c. The differences and products
+// are widened, to i128 when the differences are small enough and to 256
+// bits otherwise, so the answer is the same everywhere for any input.
+pub fn orientation(a: Vec2, b: Vec2, c: Vec2) -> Orientation {
+    let bits = |value: Fix| value.to_bits() as i128;
+    let (ux, uy) = (bits(b.x) - bits(a.x), bits(b.y) - bits(a.y));
+    let (vx, vy) = (bits(c.x) - bits(a.x), bits(c.y) - bits(a.y));
+
+    let small = |value: i128| value.abs() < 1 << 62;
+    let ordering = if small(ux) && small(uy) && small(vx) && small(vy) {
+        (ux * vy).cmp(&(uy * vx))
+    } else {
+        let cross = I256::from_i128(ux) * I256::from_i128(vy) - I256::from_i128(uy) * I256::from_i128(vx);
+        if cross.is_zero() {
+            Ordering::Equal
+        } else if cross.is_negative() {
+            Ordering::Less
+        } else {
+            Ordering::Greater
+        }
+    };
+    match ordering {
+        Ordering::Less => Orientation::Clockwise,
+        Ordering::Equal => Orientation::Collinear,
+        Ordering::Greater => Orientation::CounterClockwise,
+    }
+}
+
+// Where two primitives cross: the point, and the parameters along the
+// first and the second primitive
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Intersection {
+    pub point: Vec2,
+    pub t: Fix,
+    pub u: Fix,
+}
+
+// Segment from start (t = 0) to end (t = 1)
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Segment {
+    pub start: Vec2,
+    pub end: Vec2,
+}
+
+// Half line origin + t * direction for t >= 0
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Ray {
+    pub origin: Vec2,
+    pub direction: Vec2,
+}
+
+// Infinite line point + t * direction
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Line {
+    pub point: Vec2,
+    pub direction: Vec2,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Bounds {
+    Line,
+    Ray,
+    Segment,
+}
+
+// Shared parametric form origin + t * direction with bounds on t
+#[derive(Clone, Copy)]
+struct Parametric {
+    origin: Vec2,
+    direction: Vec2,
+    bounds: Bounds,
+}
+
+fn wide(value: Fix) -> I256 {
+    I256::from_i128(value.to_bits() as i128)
+}
+
+// Cross and dot products with 60 fractional bits, exact
+fn cross_wide(u: Vec2, v: Vec2) -> I256 {
+    wide(u.x) * wide(v.y) - wide(u.y) * wide(v.x)
+}
+
+fn dot_wide(u: Vec2, v: Vec2) -> I256 {
+    wide(u.x) * wide(v.x) + wide(u.y) * wide(v.y)
+}
+
+// numerator / denominator * scale, rounded once and saturated
+fn quotient(numerator: I256, denominator: I256, scale: I256) -> Fix {
+    match (numerator * scale).div_round(denominator) {
+        Some(bits) => Fix::from_bits(bits),
+        None if numerator.is_negative() != (denominator.is_negative() != scale.is_negative()) => Fix::MIN,
+        None => Fix::MAX,
+    }
+}
+
+impl Parametric {
+    // Whether numerator / denominator is an allowed parameter, decided
+    // exactly
+    fn accepts(self, numerator: I256, denominator: I256) -> bool {
+        let (numerator, denominator) = if denominator.is_negative() {
+            (-numerator, -denominator)
+        } else {
+            (numerator, denominator)
+        };
+        match self.bounds {
+            Bounds::Line => true,
+            Bounds::Ray => !numerator.is_negative(),
+            Bounds::Segment => !numerator.is_negative() && !(denominator - numerator).is_negative(),
+        }
+    }
+
+    // origin + direction * numerator / denominator, rounded once
+    fn point_at_fraction(self, numerator: I256, denominator: I256) -> Vec2 {
+        let step = |direction: Fix| quotient(numerator, denominator, wide(direction));
+        self.origin + Vec2::new(step(self.direction.x), step(self.direction.y))
+    }
+
+    fn intersect(self, other: Parametric) -> Option<Intersection> {
+        let denominator = cross_wide(self.direction, other.direction);
+        if denominator.is_zero() {
+            return None;
+        }
+        let offset = other.origin - self.origin;
+        let t = cross_wide(offset, other.direction);
+        let u = cross_wide(offset, self.direction);
+        if !self.accepts(t, denominator) || !other.accepts(u, denominator) {
+            return None;
+        }
+        let one = I256::from_i128(1 << 30);
+        Some(Intersection {
+            point: self.point_at_fraction(t, denominator),
+            t: quotient(t, denominator, one),
+            u: quotient(u, denominator, one),
+        })
+    }
+
+    // Closest allowed parameter to the point, as an exact fraction
+    fn closest_fraction(self, point: Vec2) -> (I256, I256) {
+        let denominator = dot_wide(self.direction, self.direction);
+        if denominator.is_zero() {
+            return (I256::ZERO, I256::from_i128(1));
+        }
+        let numerator = dot_wide(point - self.origin, self.direction);
+        match self.bounds {
+            _ if self.accepts(numerator, denominator) => (numerator, denominator),
+            Bounds::Ray | Bounds::Segment if numerator.is_negative() => (I256::ZERO, denominator),
+            _ => (denominator, denominator),
+        }
+    }
+
+    fn closest_point(self, point: Vec2) -> Vec2 {
+        let (numerator, denominator) = self.closest_fraction(point);
+        if numerator.is_zero() {
+            self.origin
+        } else if numerator == denominator {
+            self.origin + self.direction
+        } else {
+            self.point_at_fraction(numerator, denominator)
+        }
+    }
+
+    fn closest_parameter(self, point: Vec2) -> Fix {
+        let (numerator, denominator) = self.closest_fraction(point);
+        quotient(numerator, denominator, I256::from_i128(1 << 30))
+    }
+
+    fn side(self, point: Vec2) -> Orientation {
+        orientation(self.origin, self.origin + self.direction, point)
+    }
+}
+
+impl Segment {
+    pub fn new(start: Vec2, end: Vec2) -> Segment {
+        Segment { start, end }
+    }
+
+    fn parametric(self) -> Parametric {
+        Parametric { origin: self.start, direction: self.end - self.start, bounds: Bounds::Segment }
+    }
+
+    pub fn direction(self) -> Vec2 {
+        self.end - self.start
+    }
+
+    pub fn length(self) -> Fix {
+        self.direction().length()
+    }
+
+    pub fn point_at(self, t: Fix) -> Vec2 {
+        self.start + self.direction() * t
+    }
+
+    // Clockwise means right of the direction from start to end
+    pub fn side(self, point: Vec2) -> Orientation {
+        orientation(self.start, self.end, point)
+    }
+
+    pub fn closest_parameter(self, point: Vec2) -> Fix {
+        self.parametric().closest_parameter(point)
+    }
+
+    pub fn closest_point(self, point: Vec2) -> Vec2 {
+        self.parametric().closest_point(point)
+    }
+
+    pub fn distance(self, point: Vec2) -> Fix {
+        (point - self.closest_point(point)).length()
+    }
+
+    pub fn distance_squared(self, point: Vec2) -> Fix {
+        (point - self.closest_point(point)).length_squared()
+    }
+
+    // Parallel and collinear segments give None, even when they overlap
+    pub fn intersect_segment(self, other: Segment) -> Option<Intersection> {
+        self.parametric().intersect(other.parametric())
+    }
+
+    pub fn intersect_ray(self, ray: Ray) -> Option<Intersection> {
+        self.parametric().intersect(ray.parametric())
+    }
+
+    pub fn intersect_line(self, line: Line) -> Option<Intersection> {
+        self.parametric().intersect(line.parametric())
+    }
+
+    // Closest pair of points, the first on self and the second on other.
+    // Crossing segments give the crossing point twice.
+    pub fn closest_points(self, other: Segment) -> (Vec2, Vec2) {
+        if let Some(hit) = self.intersect_segment(other) {
+            return (hit.point, hit.point);
+        }
+        // Without a crossing one of the closest points is an endpoint
+        let candidates = [
+            (self.start, other.closest_point(self.start)),
+            (self.end, other.closest_point(self.end)),
+            (self.closest_point(other.start), other.start),
+            (self.closest_point(other.end), other.end),
+        ];
+        candidates.iter().cloned()
+            .min_by_key(|(a, b)| (*b - *a).length_squared_wide())
+            .unwrap()
+    }
+
+    pub fn distance_to_segment(self, other: Segment) -> Fix {
+        let (a, b) = self.closest_points(other);
+        (b - a).length()
+    }
+}
+
+impl Ray {
+    pub fn new(origin: Vec2, direction: Vec2) -> Ray {
+        Ray { origin, direction }
+    }
+
+    fn parametric(self) -> Parametric {
+        Parametric { origin: self.origin, direction: self.direction, bounds: Bounds::Ray }
+    }
+
+    pub fn point_at(self, t: Fix) -> Vec2 {
+        self.origin + self.direction * t
+    }
+
+    pub fn side(self, point: Vec2) -> Orientation {
+        self.parametric().side(point)
+    }
+
+    pub fn closest_parameter(self, point: Vec2) -> Fix {
+        self.parametric().closest_parameter(point)
+    }
+
+    pub fn closest_point(self, point: Vec2) -> Vec2 {
+        self.parametric().closest_point(point)
+    }
+
+    pub fn distance(self, point: Vec2) -> Fix {
+        (point - self.closest_point(point)).length()
+    }
+
+    pub fn intersect_segment(self, segment: Segment) -> Option<Intersection> {
+        self.parametric().intersect(segment.parametric())
+    }
+
+    pub fn intersect_ray(self, other: Ray) -> Option<Intersection> {
+        self.parametric().intersect(other.parametric())
+    }
+
+    pub fn intersect_line(self, line: Line) -> Option<Intersection> {
+        self.parametric().intersect(line.parametric())
+    }
+}
+
+impl Line {
+    pub fn new(point: Vec2, direction: Vec2) -> Line {
+        Line { point, direction }
+    }
+
+    pub fn through(a: Vec2, b: Vec2) -> Line {
+        Line { point: a, direction: b - a }
+    }
+
+    fn parametric(self) -> Parametric {
+        Parametric { origin: self.point, direction: self.direction, bounds: Bounds::Line }
+    }
+
+    pub fn point_at(self, t: Fix) -> Vec2 {
+        self.point + self.direction * t
+    }
+
+    pub fn side(self, point: Vec2) -> Orientation {
+        self.parametric().side(point)
+    }
+
+    pub fn closest_parameter(self, point: Vec2) -> Fix {
+        self.parametric().closest_parameter(point)
+    }
+
+    pub fn closest_point(self, point: Vec2) -> Vec2 {
+        self.parametric().closest_point(point)
+    }
+
+    pub fn distance(self, point: Vec2) -> Fix {
+        (point - self.closest_point(point)).length()
+    }
+
+    // Positive on the counterclockwise side of the direction, computed as
+    // cross / |direction| with a single rounding of the cross product. None
+    // when the direction is zero, as the line has no sides then.
+    pub fn signed_distance(self, point: Vec2) -> Option<Fix> {
+        if self.direction == Vec2::ZERO {
+            return None;
+        }
+        let cross = cross_wide(self.direction, point - self.point);
+        Some(quotient(cross, wide(self.direction.length()), I256::from_i128(1)))
+    }
+
+    pub fn intersect_segment(self, segment: Segment) -> Option<Intersection> {
+        self.parametric().intersect(segment.parametric())
+    }
+
+    pub fn intersect_ray(self, ray: Ray) -> Option<Intersection> {
+        self.parametric().intersect(ray.parametric())
+    }
+
+    pub fn intersect_line(self, other: Line) -> Option<Intersection> {
+        self.parametric().intersect(other.parametric())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(x: i64, y: i64) -> Vec2 {
+        Vec2::new(Fix::new(x), Fix::new(y))
+    }
+
+    #[test]
+    fn signed_distance_is_positive_on_the_left() {
+        let line = Line::new(point(1, 1), point(3, 4));
+        assert_eq!(line.signed_distance(point(-3, 4)), Some(Fix::new(5)));
+        assert_eq!(line.signed_distance(point(5, -2)), Some(Fix::new(-5)));
+        assert_eq!(line.signed_distance(point(7, 9)), Some(Fix::ZERO));
+        assert_eq!(line.distance(point(5, -2)), Fix::new(5));
+    }
+
+    #[test]
+    fn signed_distance_needs_a_direction() {
+        let degenerate = Line::new(point(1, 1), Vec2::ZERO);
+        assert_eq!(degenerate.signed_distance(point(4, 5)), None);
+        // A single bit of direction still has sides
+        let tiny = Line::new(Vec2::ZERO, Vec2::new(Fix::from_bits(1), Fix::ZERO));
+        assert_eq!(tiny.signed_distance(point(0, 2)), Some(Fix::TWO));
+        assert_eq!(tiny.signed_distance(point(0, -2)), Some(-Fix::TWO));
+    }
+
+    #[test]
+    fn intersections_respect_the_bounds() {
+        let segment = Segment::new(point(0, 0), point(4, 0));
+        let crossing = Segment::new(point(1, -1), point(1, 3));
+        let hit = segment.intersect_segment(crossing).unwrap();
+        assert_eq!((hit.point, hit.t, hit.u), (point(1, 0), Fix::ONE / Fix::new(4), Fix::ONE / Fix::new(4)));
+
+        // Touching at an end point counts, stopping short does not
+        assert!(segment.intersect_segment(Segment::new(point(4, 0), point(4, 2))).is_some());
+        assert!(segment.intersect_segment(Segment::new(point(5, -1), point(5, 1))).is_none());
+        assert!(segment.intersect_line(Line::new(point(5, -1), point(0, 1))).is_none());
+        assert!(Line::new(point(5, -1), point(0, 1)).intersect_ray(Ray::new(point(0, 0), point(1, 0))).is_some());
+        assert!(Ray::new(point(0, 0), point(-1, 0)).intersect_segment(crossing).is_none());
+        // Parallel and collinear primitives have no single crossing
+        assert!(segment.intersect_segment(Segment::new(point(0, 1), point(4, 1))).is_none());
+        assert!(segment.intersect_segment(Segment::new(point(2, 0), point(6, 0))).is_none());
+    }
+
+    #[test]
+    fn closest_points_clamp_to_the_bounds() {
+        let segment = Segment::new(point(0, 0), point(4, 0));
+        assert_eq!(segment.closest_point(point(-2, 3)), point(0, 0));
+        assert_eq!(segment.closest_point(point(2, 3)), point(2, 0));
+        assert_eq!(segment.closest_point(point(9, -1)), point(4, 0));
+        assert_eq!(segment.closest_parameter(point(1, 5)), Fix::ONE / Fix::new(4));
+        assert_eq!(Line::through(point(0, 0), point(4, 0)).closest_parameter(point(-4, 1)), -Fix::ONE);
+        assert_eq!(orientation(point(0, 0), point(1, 0), point(0, 1)), Orientation::CounterClockwise);
+        let (low, high) = (Vec2::new(Fix::MIN, Fix::MIN), Vec2::new(Fix::MAX, Fix::MAX));
+        assert_eq!(orientation(low, Vec2::ZERO, high), Orientation::Collinear);
+    }
+}
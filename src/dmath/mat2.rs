@@ -0,0 +1,217 @@
+use std::fmt;
+use std::ops;
+
+use super::fix::Fix;
+use super::vec2::Vec2;
+use super::wide::I256;
+
+// Why a matrix has no inverse in Fix
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InverseError {
+    // The determinant is exactly zero
+    Singular,
+    // An entry of the exact inverse does not fit in Fix
+    Overflow,
+}
+
+impl fmt::Display for InverseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InverseError::Singular => write!(f, "matrix is singular"),
+            InverseError::Overflow => write!(f, "matrix inverse does not fit in Fix"),
+        }
+    }
+}
+
+impl std::error::Error for InverseError {}
+
+// 2x2 matrix stored by rows, multiplying column vectors
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Mat2 {
+    pub rows: [[Fix; 2]; 2],
+}
+
+impl Mat2 {
+    pub const ZERO: Mat2 = Mat2 { rows: [[Fix::ZERO; 2]; 2] };
+    pub const IDENTITY: Mat2 = Mat2 { rows: [[Fix::ONE, Fix::ZERO], [Fix::ZERO, Fix::ONE]] };
+
+    pub fn new(m00: Fix, m01: Fix, m10: Fix, m11: Fix) -> Mat2 {
+        Mat2 { rows: [[m00, m01], [m10, m11]] }
+    }
+
+    pub fn from_cols(x_axis: Vec2, y_axis: Vec2) -> Mat2 {
+        Mat2::new(x_axis.x, y_axis.x, x_axis.y, y_axis.y)
+    }
+
+    // Counterclockwise rotation by the angle in radians
+    pub fn from_angle(angle: Fix) -> Mat2 {
+        let (sin, cos) = angle.sin_cos();
+        Mat2::new(cos, -sin, sin, cos)
+    }
+
+    pub fn from_scale(scale: Vec2) -> Mat2 {
+        Mat2::new(scale.x, Fix::ZERO, Fix::ZERO, scale.y)
+    }
+
+    // Shifts x by shear.x * y and y by shear.y * x
+    pub fn from_shear(shear: Vec2) -> Mat2 {
+        Mat2::new(Fix::ONE, shear.x, shear.y, Fix::ONE)
+    }
+
+    pub fn col(self, index: usize) -> Vec2 {
+        Vec2::new(self.rows[0][index], self.rows[1][index])
+    }
+
+    pub fn row(self, index: usize) -> Vec2 {
+        Vec2::new(self.rows[index][0], self.rows[index][1])
+    }
+
+    pub fn transpose(self) -> Mat2 {
+        Mat2::new(self.rows[0][0], self.rows[1][0], self.rows[0][1], self.rows[1][1])
+    }
+
+    pub fn determinant(self) -> Fix {
+        self.rows[0][0] * self.rows[1][1] - self.rows[0][1] * self.rows[1][0]
+    }
+
+    // Exact determinant with 60 fractional bits
+    pub(crate) fn determinant_wide(self) -> I256 {
+        let [[a, b], [c, d]] = self.rows;
+        wide(a) * wide(d) - wide(b) * wide(c)
+    }
+
+    // Each entry is the exact inverse rounded once to Fix
+    pub fn inverse(self) -> Result<Mat2, InverseError> {
+        let determinant = self.determinant_wide();
+        if determinant.is_zero() {
+            return Err(InverseError::Singular);
+        }
+        let [[a, b], [c, d]] = self.rows;
+        let entry = |value: I256| {
+            value.shl(60).div_round(determinant).map(Fix::from_bits).ok_or(InverseError::Overflow)
+        };
+        Ok(Mat2::new(entry(wide(d))?, entry(-wide(b))?, entry(-wide(c))?, entry(wide(a))?))
+    }
+
+    // Component-wise, t outside [0, 1] extrapolates
+    pub fn lerp(self, other: Mat2, t: Fix) -> Mat2 {
+        self + (other - self) * t
+    }
+}
+
+fn wide(value: Fix) -> I256 {
+    I256::from_i128(value.to_bits() as i128)
+}
+
+impl fmt::Display for Mat2 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[[{}, {}], [{}, {}]]", self.rows[0][0], self.rows[0][1], self.rows[1][0], self.rows[1][1])
+    }
+}
+
+impl ops::Add for Mat2 {
+    type Output = Mat2;
+
+    fn add(self, _rhs: Mat2) -> Mat2 {
+        let mut result = self;
+        for (row, other) in result.rows.iter_mut().zip(_rhs.rows.iter()) {
+            for (value, other) in row.iter_mut().zip(other.iter()) {
+                *value += *other;
+            }
+        }
+        result
+    }
+}
+
+impl ops::Sub for Mat2 {
+    type Output = Mat2;
+
+    fn sub(self, _rhs: Mat2) -> Mat2 {
+        self + -_rhs
+    }
+}
+
+impl ops::Neg for Mat2 {
+    type Output = Mat2;
+
+    fn neg(self) -> Mat2 {
+        self * -Fix::ONE
+    }
+}
+
+impl ops::Mul<Fix> for Mat2 {
+    type Output = Mat2;
+
+    fn mul(self, _rhs: Fix) -> Mat2 {
+        let mut result = self;
+        for value in result.rows.iter_mut().flatten() {
+            *value *= _rhs;
+        }
+        result
+    }
+}
+
+impl ops::Mul<Vec2> for Mat2 {
+    type Output = Vec2;
+
+    fn mul(self, _rhs: Vec2) -> Vec2 {
+        Vec2::new(self.row(0).dot(_rhs), self.row(1).dot(_rhs))
+    }
+}
+
+// self * other applies other first
+impl ops::Mul for Mat2 {
+    type Output = Mat2;
+
+    fn mul(self, _rhs: Mat2) -> Mat2 {
+        Mat2::from_cols(self * _rhs.col(0), self * _rhs.col(1))
+    }
+}
+
+impl ops::MulAssign for Mat2 {
+    fn mul_assign(&mut self, _rhs: Mat2) {
+        *self = *self * _rhs;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: Mat2, b: Mat2, ulps: i64) -> bool {
+        a.rows.iter().flatten().zip(b.rows.iter().flatten()).all(|(x, y)| (x.to_bits() - y.to_bits()).abs() <= ulps)
+    }
+
+    #[test]
+    fn inverse_is_exact_when_representable() {
+        let scale = Mat2::from_scale(Vec2::new(Fix::new(2), Fix::new(-4)));
+        let expected = Mat2::from_scale(Vec2::new(Fix::HALF, -(Fix::HALF * Fix::HALF)));
+        assert_eq!(scale.inverse(), Ok(expected));
+        assert_eq!(expected.inverse(), Ok(scale));
+        assert_eq!(Mat2::IDENTITY.inverse(), Ok(Mat2::IDENTITY));
+    }
+
+    #[test]
+    fn inverse_undoes_the_matrix() {
+        // The table sine and cosine are within 8e-8 of a unit vector, so
+        // the determinant is not quite one
+        let rotation = Mat2::from_angle(Fix::new(1));
+        assert!(close(rotation.inverse().unwrap(), rotation.transpose(), 256));
+        let skewed = Mat2::new(Fix::new(3), Fix::new(7), -Fix::HALF, Fix::new(5));
+        assert!(close(skewed * skewed.inverse().unwrap(), Mat2::IDENTITY, 8));
+        assert!(close(skewed.inverse().unwrap() * skewed, Mat2::IDENTITY, 8));
+    }
+
+    #[test]
+    fn inverse_tells_singular_from_overflow() {
+        assert_eq!(Mat2::ZERO.inverse(), Err(InverseError::Singular));
+        assert_eq!(Mat2::new(Fix::ONE, Fix::TWO, Fix::TWO, Fix::new(4)).inverse(), Err(InverseError::Singular));
+        // Determinant of -2^-60, the inverse entries are near 2^60
+        let epsilon = Fix::from_bits(1);
+        let nearly = Mat2::new(Fix::ONE + epsilon, Fix::ONE, Fix::ONE, Fix::ONE - epsilon);
+        assert_eq!(nearly.inverse(), Err(InverseError::Overflow));
+        // The smallest determinant that is not zero still inverts
+        let tiny = Mat2::new(epsilon, Fix::ZERO, Fix::ZERO, epsilon);
+        assert_eq!(tiny.inverse().unwrap().rows[0][0], Fix::new(1 << 30));
+    }
+}
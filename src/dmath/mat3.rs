@@ -0,0 +1,235 @@
+use std::fmt;
+use std::ops;
+
+use super::fix::Fix;
+use super::mat2::InverseError;
+use super::vec2::Vec2;
+use super::wide::I256;
+
+// 3x3 matrix stored by rows, multiplying column vectors. As a 2D transform
+// points are (x, y, 1) and vectors (x, y, 0).
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Mat3 {
+    pub rows: [[Fix; 3]; 3],
+}
+
+impl Mat3 {
+    pub const ZERO: Mat3 = Mat3 { rows: [[Fix::ZERO; 3]; 3] };
+    pub const IDENTITY: Mat3 = Mat3 {
+        rows: [
+            [Fix::ONE, Fix::ZERO, Fix::ZERO],
+            [Fix::ZERO, Fix::ONE, Fix::ZERO],
+            [Fix::ZERO, Fix::ZERO, Fix::ONE],
+        ],
+    };
+
+    pub fn from_rows(rows: [[Fix; 3]; 3]) -> Mat3 {
+        Mat3 { rows }
+    }
+
+    pub fn transpose(self) -> Mat3 {
+        let mut result = self;
+        for (i, row) in self.rows.iter().enumerate() {
+            for (j, value) in row.iter().enumerate() {
+                result.rows[j][i] = *value;
+            }
+        }
+        result
+    }
+
+    pub fn determinant(self) -> Fix {
+        let m = &self.rows;
+        m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+            - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+            + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+    }
+
+    // Cofactors with 60 fractional bits, exact
+    fn cofactors(self) -> [[I256; 3]; 3] {
+        let m = &self.rows;
+        let wide = |row: usize, col: usize| I256::from_i128(m[row][col].to_bits() as i128);
+        let mut result = [[I256::ZERO; 3]; 3];
+        for (i, row) in result.iter_mut().enumerate() {
+            for (j, value) in row.iter_mut().enumerate() {
+                let (r0, r1) = ((i + 1) % 3, (i + 2) % 3);
+                let (c0, c1) = ((j + 1) % 3, (j + 2) % 3);
+                // The cyclic order of the minors already carries the sign
+                *value = wide(r0, c0) * wide(r1, c1) - wide(r0, c1) * wide(r1, c0);
+            }
+        }
+        result
+    }
+
+    // Each entry is the exact inverse rounded once to Fix
+    pub fn inverse(self) -> Result<Mat3, InverseError> {
+        let cofactors = self.cofactors();
+        // Determinant with 90 fractional bits
+        let determinant = self.rows[0].iter().zip(cofactors[0].iter())
+            .fold(I256::ZERO, |sum, (value, cofactor)| {
+                sum + I256::from_i128(value.to_bits() as i128) * *cofactor
+            });
+        if determinant.is_zero() {
+            return Err(InverseError::Singular);
+        }
+        let mut result = Mat3::ZERO;
+        for (i, row) in result.rows.iter_mut().enumerate() {
+            for (j, value) in row.iter_mut().enumerate() {
+                let entry = cofactors[j][i].shl(60).div_round(determinant).ok_or(InverseError::Overflow)?;
+                *value = Fix::from_bits(entry);
+            }
+        }
+        Ok(result)
+    }
+
+    // Divides by w unless the bottom row is (0, 0, 1), saturating when w
+    // is zero
+    pub fn transform_point(self, point: Vec2) -> Vec2 {
+        let m = &self.rows;
+        let x = m[0][0] * point.x + m[0][1] * point.y + m[0][2];
+        let y = m[1][0] * point.x + m[1][1] * point.y + m[1][2];
+        if m[2][0] == Fix::ZERO && m[2][1] == Fix::ZERO && m[2][2] == Fix::ONE {
+            return Vec2::new(x, y);
+        }
+        let w = m[2][0] * point.x + m[2][1] * point.y + m[2][2];
+        Vec2::new(x.saturating_div(w), y.saturating_div(w))
+    }
+
+    // Ignores the translation column
+    pub fn transform_vector(self, vector: Vec2) -> Vec2 {
+        let m = &self.rows;
+        Vec2::new(
+            m[0][0] * vector.x + m[0][1] * vector.y,
+            m[1][0] * vector.x + m[1][1] * vector.y,
+        )
+    }
+
+    // Component-wise, t outside [0, 1] extrapolates
+    pub fn lerp(self, other: Mat3, t: Fix) -> Mat3 {
+        let mut result = self;
+        for (value, other) in result.rows.iter_mut().flatten().zip(other.rows.iter().flatten()) {
+            *value += (*other - *value) * t;
+        }
+        result
+    }
+}
+
+impl fmt::Display for Mat3 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[")?;
+        for (i, row) in self.rows.iter().enumerate() {
+            let separator = if i > 0 { ", " } else { "" };
+            write!(f, "{}[{}, {}, {}]", separator, row[0], row[1], row[2])?;
+        }
+        write!(f, "]")
+    }
+}
+
+impl ops::Add for Mat3 {
+    type Output = Mat3;
+
+    fn add(self, _rhs: Mat3) -> Mat3 {
+        let mut result = self;
+        for (value, other) in result.rows.iter_mut().flatten().zip(_rhs.rows.iter().flatten()) {
+            *value += *other;
+        }
+        result
+    }
+}
+
+impl ops::Sub for Mat3 {
+    type Output = Mat3;
+
+    fn sub(self, _rhs: Mat3) -> Mat3 {
+        let mut result = self;
+        for (value, other) in result.rows.iter_mut().flatten().zip(_rhs.rows.iter().flatten()) {
+            *value -= *other;
+        }
+        result
+    }
+}
+
+impl ops::Mul<Fix> for Mat3 {
+    type Output = Mat3;
+
+    fn mul(self, _rhs: Fix) -> Mat3 {
+        let mut result = self;
+        for value in result.rows.iter_mut().flatten() {
+            *value *= _rhs;
+        }
+        result
+    }
+}
+
+// self * other applies other first
+impl ops::Mul for Mat3 {
+    type Output = Mat3;
+
+    fn mul(self, _rhs: Mat3) -> Mat3 {
+        let mut result = Mat3::ZERO;
+        for (row, left) in result.rows.iter_mut().zip(self.rows.iter()) {
+            for (j, value) in row.iter_mut().enumerate() {
+                *value = left[0] * _rhs.rows[0][j] + left[1] * _rhs.rows[1][j] + left[2] * _rhs.rows[2][j];
+            }
+        }
+        result
+    }
+}
+
+impl ops::MulAssign for Mat3 {
+    fn mul_assign(&mut self, _rhs: Mat3) {
+        *self = *self * _rhs;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: Mat3, b: Mat3, ulps: i64) -> bool {
+        a.rows.iter().flatten().zip(b.rows.iter().flatten()).all(|(x, y)| (x.to_bits() - y.to_bits()).abs() <= ulps)
+    }
+
+    #[test]
+    fn inverse_undoes_the_matrix() {
+        let matrix = Mat3::from_rows([
+            [Fix::new(2), Fix::ONE, Fix::new(-3)],
+            [Fix::ZERO, Fix::new(4), Fix::HALF],
+            [Fix::ONE, Fix::ZERO, Fix::new(6)],
+        ]);
+        let inverse = matrix.inverse().unwrap();
+        assert!(close(matrix * inverse, Mat3::IDENTITY, 16));
+        assert!(close(inverse * matrix, Mat3::IDENTITY, 16));
+        assert!(close(inverse.inverse().unwrap(), matrix, 16));
+
+        let diagonal = Mat3::from_rows([
+            [Fix::TWO, Fix::ZERO, Fix::ZERO],
+            [Fix::ZERO, Fix::new(4), Fix::ZERO],
+            [Fix::ZERO, Fix::ZERO, Fix::ONE],
+        ]);
+        let halved = Mat3::from_rows([
+            [Fix::HALF, Fix::ZERO, Fix::ZERO],
+            [Fix::ZERO, Fix::HALF * Fix::HALF, Fix::ZERO],
+            [Fix::ZERO, Fix::ZERO, Fix::ONE],
+        ]);
+        assert_eq!(diagonal.inverse(), Ok(halved));
+    }
+
+    #[test]
+    fn inverse_tells_singular_from_overflow() {
+        assert_eq!(Mat3::ZERO.inverse(), Err(InverseError::Singular));
+        let dependent = Mat3::from_rows([
+            [Fix::ONE, Fix::TWO, Fix::new(3)],
+            [Fix::new(2), Fix::new(4), Fix::new(6)],
+            [Fix::ZERO, Fix::ONE, Fix::ONE],
+        ]);
+        assert_eq!(dependent.inverse(), Err(InverseError::Singular));
+
+        let epsilon = Fix::from_bits(1);
+        let nearly = Mat3::from_rows([
+            [Fix::ONE + epsilon, Fix::ONE, Fix::ZERO],
+            [Fix::ONE, Fix::ONE - epsilon, Fix::ZERO],
+            [Fix::ZERO, Fix::ZERO, Fix::ONE],
+        ]);
+        assert_eq!(nearly.inverse(), Err(InverseError::Overflow));
+    }
+}
@@ -1,2 +1,31 @@
+pub mod aabb;
+pub mod affine2;
+pub mod angle;
+pub mod clip;
+pub mod complex;
+pub mod curve;
+pub mod fft;
 pub mod fix;
-mod lookup;
\ No newline at end of file
+pub mod fix32;
+pub mod line;
+pub mod mat2;
+pub mod mat3;
+pub mod parse;
+pub mod polygon;
+pub mod rotation;
+pub mod vec2;
+mod lookup;
+mod wide;
+
+pub use aabb::Aabb;
+pub use affine2::{Affine2, Trs};
+pub use angle::Angle;
+pub use clip::{FillRule, Join};
+pub use complex::Complex;
+pub use curve::{ArcLengthTable, BSpline, CatmullRom, CubicBezier, Curve, QuadraticBezier};
+pub use line::{Intersection, Line, Orientation, Ray, Segment};
+pub use mat2::{InverseError, Mat2};
+pub use mat3::Mat3;
+pub use polygon::Polygon;
+pub use rotation::Rotation;
+pub use vec2::Vec2;
@@ -0,0 +1,300 @@
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use super::fix::Fixed;
+use super::fix32::Fix32;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseFixErrorKind {
+    Empty,
+    InvalidDigit,
+    MissingDigits,
+    Overflow,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseFixError {
+    kind: ParseFixErrorKind,
+    position: usize,
+}
+
+impl ParseFixError {
+    fn new(kind: ParseFixErrorKind, position: usize) -> ParseFixError {
+        ParseFixError { kind, position }
+    }
+
+    pub fn kind(&self) -> ParseFixErrorKind {
+        self.kind
+    }
+
+    // Byte offset into the parsed string where the problem was found
+    pub fn position(&self) -> usize {
+        self.position
+    }
+}
+
+impl fmt::Display for ParseFixError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let description = match self.kind {
+            ParseFixErrorKind::Empty => "cannot parse fixed-point number from empty string",
+            ParseFixErrorKind::InvalidDigit => "invalid character in fixed-point number",
+            ParseFixErrorKind::MissingDigits => "expected digits in fixed-point number",
+            ParseFixErrorKind::Overflow => "fixed-point number too large to fit in target type",
+        };
+        write!(f, "{} at position {}", description, self.position)
+    }
+}
+
+impl Error for ParseFixError {}
+
+// Accepted syntax, surrounded by optional whitespace:
+//   [+-] digits [. digits] [(e|E) [+-] digits]   decimal, rounded half to even
+//   [+-] 0x hexdigits | 0b bindigits             raw two's complement bits
+// The result is sign-extended from a `width` bit backing integer.
+pub(crate) fn parse_bits(input: &str, frac: u32, width: u32) -> Result<i64, ParseFixError> {
+    let bytes = input.as_bytes();
+    let mut end = bytes.len();
+    while end > 0 && bytes[end - 1].is_ascii_whitespace() {
+        end -= 1;
+    }
+    let mut pos = 0;
+    while pos < end && bytes[pos].is_ascii_whitespace() {
+        pos += 1;
+    }
+    if pos == end {
+        return Err(ParseFixError::new(ParseFixErrorKind::Empty, pos));
+    }
+
+    let number_start = pos;
+    let negative = bytes[pos] == b'-';
+    if negative || bytes[pos] == b'+' {
+        pos += 1;
+    }
+
+    if pos + 1 < end && bytes[pos] == b'0' {
+        match bytes[pos + 1] {
+            b'x' | b'X' => return parse_raw(bytes, pos + 2, end, 16, width, negative),
+            b'b' | b'B' => return parse_raw(bytes, pos + 2, end, 2, width, negative),
+            _ => {}
+        }
+    }
+
+    let mut digits: Vec<u8> = Vec::new();
+    let mut point: i64 = 0;
+
+    let int_start = pos;
+    while pos < end && bytes[pos].is_ascii_digit() {
+        digits.push(bytes[pos] - b'0');
+        pos += 1;
+    }
+    point += (pos - int_start) as i64;
+
+    if pos < end && bytes[pos] == b'.' {
+        pos += 1;
+        while pos < end && bytes[pos].is_ascii_digit() {
+            digits.push(bytes[pos] - b'0');
+            pos += 1;
+        }
+    }
+    if digits.is_empty() {
+        return Err(ParseFixError::new(ParseFixErrorKind::MissingDigits, pos));
+    }
+
+    if pos < end && (bytes[pos] == b'e' || bytes[pos] == b'E') {
+        pos += 1;
+        let exp_negative = pos < end && bytes[pos] == b'-';
+        if pos < end && (bytes[pos] == b'-' || bytes[pos] == b'+') {
+            pos += 1;
+        }
+        let exp_start = pos;
+        let mut exponent: i64 = 0;
+        while pos < end && bytes[pos].is_ascii_digit() {
+            // Anything this large is zero or an overflow either way
+            exponent = (exponent * 10 + (bytes[pos] - b'0') as i64).min(1 << 32);
+            pos += 1;
+        }
+        if pos == exp_start {
+            return Err(ParseFixError::new(ParseFixErrorKind::MissingDigits, pos));
+        }
+        point += if exp_negative { -exponent } else { exponent };
+    }
+
+    if pos < end {
+        return Err(ParseFixError::new(ParseFixErrorKind::InvalidDigit, pos));
+    }
+
+    let overflow = ParseFixError::new(ParseFixErrorKind::Overflow, number_start);
+
+    let leading_zeros = digits.iter().take_while(|&&d| d == 0).count();
+    digits.drain(..leading_zeros);
+    point -= leading_zeros as i64;
+    if digits.is_empty() {
+        return Ok(0);
+    }
+
+    // Integer part, with implicit zeros when the point is past the digits
+    let mut integral: u128 = 0;
+    if point > 20 {
+        return Err(overflow);
+    }
+    for i in 0..point.max(0) as usize {
+        integral = integral * 10 + *digits.get(i).unwrap_or(&0) as u128;
+    }
+    if integral >= 1 << (width - 1) {
+        return Err(overflow);
+    }
+
+    // Fraction digits, with implicit zeros when the point is before the
+    // digits. Keeping frac + 1 digits is enough to decide the rounding
+    // exactly: every halfway point between two results has at most that
+    // many decimals, so dropped digits only matter as a sticky bit.
+    let keep = frac as usize + 1;
+    let mut fraction: Vec<u8> = Vec::with_capacity(keep);
+    let mut sticky = false;
+    let mut index = point.max(-(keep as i64) - 1);
+    while index < digits.len() as i64 {
+        let digit = if index < 0 { 0 } else { digits[index as usize] };
+        if fraction.len() < keep {
+            fraction.push(digit);
+        } else if digit != 0 {
+            sticky = true;
+            break;
+        }
+        index += 1;
+    }
+
+    // Shift the fraction out one bit at a time by doubling the decimals
+    let mut bits: u128 = 0;
+    for _ in 0..frac {
+        let mut carry = 0;
+        for digit in fraction.iter_mut().rev() {
+            let doubled = *digit * 2 + carry;
+            *digit = doubled % 10;
+            carry = doubled / 10;
+        }
+        bits = (bits << 1) | carry as u128;
+    }
+
+    // What is left of the decimals is the remainder below one bit
+    let exactly_half = !sticky && fraction.iter().skip(1).all(|&d| d == 0);
+    let round_up = match fraction.first() {
+        Some(&first) if first > 5 => true,
+        Some(&5) => !exactly_half || bits & 1 == 1,
+        _ => false,
+    };
+    if round_up {
+        bits += 1;
+    }
+
+    let magnitude = (integral << frac) + bits;
+    let limit = if negative { 1u128 << (width - 1) } else { (1u128 << (width - 1)) - 1 };
+    if magnitude > limit {
+        return Err(overflow);
+    }
+    Ok(if negative { (magnitude as i64).wrapping_neg() } else { magnitude as i64 })
+}
+
+fn parse_raw(bytes: &[u8], start: usize, end: usize, radix: u32, width: u32, negative: bool)
+    -> Result<i64, ParseFixError> {
+    if start == end {
+        return Err(ParseFixError::new(ParseFixErrorKind::MissingDigits, start));
+    }
+    let mut raw: u64 = 0;
+    for (pos, &byte) in bytes.iter().enumerate().take(end).skip(start) {
+        let digit = (byte as char).to_digit(radix)
+            .ok_or_else(|| ParseFixError::new(ParseFixErrorKind::InvalidDigit, pos))?;
+        raw = raw.checked_mul(radix as u64)
+            .and_then(|r| r.checked_add(digit as u64))
+            .filter(|&r| width == 64 || r >> width == 0)
+            .ok_or_else(|| ParseFixError::new(ParseFixErrorKind::Overflow, pos))?;
+    }
+    let unused = 64 - width;
+    let bits = ((raw << unused) as i64) >> unused;
+    Ok(if negative { bits.wrapping_neg() } else { bits })
+}
+
+impl<const FRAC: u32> FromStr for Fixed<FRAC> {
+    type Err = ParseFixError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        parse_bits(value, FRAC, 64).map(Fixed::from_bits)
+    }
+}
+
+impl<const FRAC: u32> TryFrom<&str> for Fixed<FRAC> {
+    type Error = ParseFixError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl FromStr for Fix32 {
+    type Err = ParseFixError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        parse_bits(value, 16, 32).map(|bits| Fix32::from_bits(bits as i32))
+    }
+}
+
+impl TryFrom<&str> for Fix32 {
+    type Error = ParseFixError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dmath::fix::Fix;
+
+    fn error(kind: ParseFixErrorKind, position: usize) -> ParseFixError {
+        ParseFixError::new(kind, position)
+    }
+
+    #[test]
+    fn decimals_round_half_to_even() {
+        let bits = |text: &str| text.parse::<Fixed<1>>().unwrap().to_bits();
+        assert_eq!(bits("0.25"), 0);
+        assert_eq!(bits("0.75"), 2);
+        assert_eq!(bits("-0.75"), -2);
+        assert_eq!(bits("0.2500000000000000000000000000001"), 1);
+        assert_eq!(bits("1.24999"), 2);
+        assert_eq!(" +1.5 ".parse::<Fix>(), Ok(Fix::new(3) / Fix::new(2)));
+        assert_eq!("15e-1".parse::<Fix>(), "1.5".parse::<Fix>());
+        assert_eq!("0.015E2".parse::<Fix>(), "1.5".parse::<Fix>());
+        assert_eq!("1e-99999999999".parse::<Fix>(), Ok(Fix::ZERO));
+    }
+
+    #[test]
+    fn raw_bits_are_sign_extended_from_the_backing_width() {
+        assert_eq!("0x40000000".parse::<Fix>(), Ok(Fix::ONE));
+        assert_eq!("-0b1".parse::<Fix>(), Ok(Fix::from_bits(-1)));
+        assert_eq!("0xFFFFFFFF".parse::<Fix32>(), Ok(Fix32::from_bits(-1)));
+        assert_eq!("0x8000000000000000".parse::<Fix>(), Ok(Fix::MIN));
+        assert_eq!("0x100000000".parse::<Fix32>(), Err(error(ParseFixErrorKind::Overflow, 10)));
+    }
+
+    #[test]
+    fn limits_and_errors() {
+        assert_eq!("-8589934592".parse::<Fix>(), Ok(Fix::MIN));
+        assert_eq!(" 8589934592".parse::<Fix>(), Err(error(ParseFixErrorKind::Overflow, 1)));
+        assert_eq!("-32768".parse::<Fix32>(), Ok(Fix32::MIN));
+        assert_eq!("32767.99999".parse::<Fix32>(), Ok(Fix32::MAX));
+        assert_eq!("32767.999995".parse::<Fix32>(), Err(error(ParseFixErrorKind::Overflow, 0)));
+
+        assert_eq!("".parse::<Fix>(), Err(error(ParseFixErrorKind::Empty, 0)));
+        assert_eq!("   ".parse::<Fix>(), Err(error(ParseFixErrorKind::Empty, 0)));
+        assert_eq!("-".parse::<Fix>(), Err(error(ParseFixErrorKind::MissingDigits, 1)));
+        assert_eq!(".".parse::<Fix>(), Err(error(ParseFixErrorKind::MissingDigits, 1)));
+        assert_eq!("1e".parse::<Fix>(), Err(error(ParseFixErrorKind::MissingDigits, 2)));
+        assert_eq!("0x".parse::<Fix>(), Err(error(ParseFixErrorKind::MissingDigits, 2)));
+        assert_eq!("1.2.3".parse::<Fix>(), Err(error(ParseFixErrorKind::InvalidDigit, 3)));
+        assert_eq!("0x1g".parse::<Fix>(), Err(error(ParseFixErrorKind::InvalidDigit, 3)));
+        assert_eq!(Fix::try_from("1 2").unwrap_err().position(), 1);
+    }
+}
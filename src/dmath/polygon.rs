@@ -0,0 +1,385 @@
+use super::aabb::Aabb;
+use super::fix::Fix;
+use super::line::{orientation, Orientation};
+use super::vec2::Vec2;
+use super::wide::I256;
+
+// Simple polygon as a closed loop of points, without repeating the first
+// point at the end. Every decision that depends on which side of an edge a
+// point is goes through the exact orientation predicate.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct Polygon {
+    pub points: Vec<Vec2>,
+}
+
+fn wide(value: Fix) -> I256 {
+    I256::from_i128(value.to_bits() as i128)
+}
+
+impl Polygon {
+    pub fn new(points: Vec<Vec2>) -> Polygon {
+        Polygon { points }
+    }
+
+    fn edges(&self) -> impl Iterator<Item = (Vec2, Vec2)> + '_ {
+        let count = self.points.len();
+        (0..count).map(move |i| (self.points[i], self.points[(i + 1) % count]))
+    }
+
+    // Twice the signed area with 60 fractional bits, exact
+    fn double_area_wide(&self) -> I256 {
+        self.edges().fold(I256::ZERO, |sum, (a, b)| sum + wide(a.x) * wide(b.y) - wide(a.y) * wide(b.x))
+    }
+
+    // Positive for counterclockwise winding, rounded once and saturated
+    pub fn signed_area(&self) -> Fix {
+        let double_area = self.double_area_wide();
+        match double_area.div_round(I256::from_i128(1 << 31)) {
+            Some(bits) => Fix::from_bits(bits),
+            None if double_area.is_negative() => Fix::MIN,
+            None => Fix::MAX,
+        }
+    }
+
+    pub fn area(&self) -> Fix {
+        Fix::abs(self.signed_area())
+    }
+
+    pub fn perimeter(&self) -> Fix {
+        self.edges().fold(Fix::ZERO, |sum, (a, b)| sum + (b - a).length())
+    }
+
+    // Collinear for polygons with no area
+    pub fn winding(&self) -> Orientation {
+        let double_area = self.double_area_wide();
+        if double_area.is_zero() {
+            Orientation::Collinear
+        } else if double_area.is_negative() {
+            Orientation::Clockwise
+        } else {
+            Orientation::CounterClockwise
+        }
+    }
+
+    pub fn reverse(&mut self) {
+        self.points.reverse();
+    }
+
+    // Reverses clockwise polygons so they wind counterclockwise
+    pub fn make_counter_clockwise(&mut self) {
+        if self.winding() == Orientation::Clockwise {
+            self.reverse();
+        }
+    }
+
+    // Area centroid, rounded once per coordinate. None for polygons with
+    // no area or a centroid out of range.
+    pub fn centroid(&self) -> Option<Vec2> {
+        let double_area = self.double_area_wide();
+        if double_area.is_zero() {
+            return None;
+        }
+        let mut x = I256::ZERO;
+        let mut y = I256::ZERO;
+        for (a, b) in self.edges() {
+            let cross = wide(a.x) * wide(b.y) - wide(a.y) * wide(b.x);
+            x = x + (wide(a.x) + wide(b.x)) * cross;
+            y = y + (wide(a.y) + wide(b.y)) * cross;
+        }
+        let divisor = I256::from_i128(3) * double_area;
+        Some(Vec2::new(Fix::from_bits(x.div_round(divisor)?), Fix::from_bits(y.div_round(divisor)?)))
+    }
+
+    pub fn aabb(&self) -> Option<Aabb> {
+        Aabb::from_points(&self.points)
+    }
+
+    // Nonzero winding rule, points on the boundary are inside
+    pub fn contains_point(&self, point: Vec2) -> bool {
+        let mut winding = 0;
+        for (a, b) in self.edges() {
+            let side = orientation(a, b, point);
+            if side == Orientation::Collinear && Aabb::from_corners(a, b).contains_point(point) {
+                return true;
+            }
+            if a.y <= point.y && point.y < b.y && side == Orientation::CounterClockwise {
+                winding += 1;
+            } else if b.y <= point.y && point.y < a.y && side == Orientation::Clockwise {
+                winding -= 1;
+            }
+        }
+        winding != 0
+    }
+
+    // True when no vertex turns against the winding. Collinear vertices are
+    // allowed.
+    pub fn is_convex(&self) -> bool {
+        let count = self.points.len();
+        let mut turn = Orientation::Collinear;
+        for i in 0..count {
+            let side = orientation(self.points[i], self.points[(i + 1) % count], self.points[(i + 2) % count]);
+            if side == Orientation::Collinear {
+                continue;
+            }
+            if turn != Orientation::Collinear && side != turn {
+                return false;
+            }
+            turn = side;
+        }
+        true
+    }
+
+    // Andrew's monotone chain. The hull winds counterclockwise from the
+    // lowest x, then lowest y, point and leaves out collinear points.
+    pub fn convex_hull(points: &[Vec2]) -> Polygon {
+        let mut sorted = points.to_vec();
+        sorted.sort_by_key(|point| (point.x, point.y));
+        sorted.dedup();
+        if sorted.len() < 3 {
+            return Polygon::new(sorted);
+        }
+
+        // Lower chain left to right, then upper chain right to left. The
+        // last point of each chain starts the other one.
+        let mut hull: Vec<Vec2> = Vec::with_capacity(sorted.len() + 1);
+        let mut add_chain = |chain: &mut dyn Iterator<Item = &Vec2>| {
+            let start = hull.len();
+            for &point in chain {
+                while hull.len() >= start + 2
+                    && orientation(hull[hull.len() - 2], hull[hull.len() - 1], point) != Orientation::CounterClockwise {
+                    hull.pop();
+                }
+                hull.push(point);
+            }
+            hull.pop();
+        };
+        add_chain(&mut sorted.iter());
+        add_chain(&mut sorted.iter().rev());
+        Polygon::new(hull)
+    }
+
+    // Ear clipping. Returns counterclockwise triangles as indices into
+    // points, whatever the winding of the polygon. Meant for simple
+    // polygons; degenerate input still terminates but may leave gaps.
+    pub fn triangulate(&self) -> Vec<[usize; 3]> {
+        let mut remaining: Vec<usize> = (0..self.points.len()).collect();
+        if self.winding() == Orientation::Clockwise {
+            remaining.reverse();
+        }
+        let mut triangles = Vec::with_capacity(self.points.len().saturating_sub(2));
+
+        while remaining.len() > 3 {
+            let count = remaining.len();
+            let corner = |k: usize| {
+                [remaining[(k + count - 1) % count], remaining[k], remaining[(k + 1) % count]]
+            };
+            let ear = (0..count).find(|&k| self.is_ear(&remaining, corner(k)));
+            match ear {
+                Some(k) => {
+                    triangles.push(corner(k));
+                    remaining.remove(k);
+                }
+                None => {
+                    // No ear means degenerate input: drop a collinear vertex
+                    // if there is one, otherwise give up on a vertex
+                    let flat = (0..count).find(|&k| {
+                        let [a, b, c] = corner(k);
+                        orientation(self.points[a], self.points[b], self.points[c]) == Orientation::Collinear
+                    });
+                    remaining.remove(flat.unwrap_or(0));
+                }
+            }
+        }
+        if remaining.len() == 3 {
+            let [a, b, c] = [remaining[0], remaining[1], remaining[2]];
+            if orientation(self.points[a], self.points[b], self.points[c]) == Orientation::CounterClockwise {
+                triangles.push([a, b, c]);
+            }
+        }
+        triangles
+    }
+
+    fn is_ear(&self, remaining: &[usize], [a, b, c]: [usize; 3]) -> bool {
+        let (pa, pb, pc) = (self.points[a], self.points[b], self.points[c]);
+        if orientation(pa, pb, pc) != Orientation::CounterClockwise {
+            return false;
+        }
+        // No other vertex may touch the triangle
+        remaining.iter()
+            .filter(|&&i| i != a && i != b && i != c)
+            .map(|&i| self.points[i])
+            .filter(|&p| p != pa && p != pb && p != pc)
+            .all(|p| {
+                orientation(pa, pb, p) == Orientation::Clockwise
+                    || orientation(pb, pc, p) == Orientation::Clockwise
+                    || orientation(pc, pa, p) == Orientation::Clockwise
+            })
+    }
+
+    // Hertel-Mehlhorn: triangulate, then remove diagonals whenever the two
+    // pieces on either side merge into a convex piece. The result has at
+    // most four times the minimum number of pieces, all counterclockwise.
+    pub fn convex_decomposition(&self) -> Vec<Polygon> {
+        let mut pieces: Vec<Vec<usize>> = self.triangulate().iter().map(|t| t.to_vec()).collect();
+
+        let mut merged = true;
+        while merged {
+            merged = false;
+            'search: for i in 0..pieces.len() {
+                for j in i + 1..pieces.len() {
+                    if let Some(piece) = self.merge_convex(&pieces[i], &pieces[j]) {
+                        pieces[i] = piece;
+                        pieces.remove(j);
+                        merged = true;
+                        break 'search;
+                    }
+                }
+            }
+        }
+
+        pieces.iter()
+            .map(|piece| Polygon::new(piece.iter().map(|&i| self.points[i]).collect()))
+            .collect()
+    }
+
+    // Joins two counterclockwise pieces along a shared edge, if they share
+    // one and the result stays convex
+    fn merge_convex(&self, first: &[usize], second: &[usize]) -> Option<Vec<usize>> {
+        let (n, m) = (first.len(), second.len());
+        // Edge a -> b in the first piece, running b -> a in the second
+        let (i, j) = (0..n).find_map(|i| {
+            let (a, b) = (first[i], first[(i + 1) % n]);
+            (0..m).find(|&j| second[j] == b && second[(j + 1) % m] == a).map(|j| (i, j))
+        })?;
+
+        // b and the rest of the first piece round to a, then the second
+        // piece strictly between a and b
+        let mut piece: Vec<usize> = (1..=n).map(|k| first[(i + k) % n]).collect();
+        piece.extend((2..m).map(|k| second[(j + k) % m]));
+
+        let count = piece.len();
+        let convex = (0..count).all(|k| {
+            let [a, b, c] = [piece[(k + count - 1) % count], piece[k], piece[(k + 1) % count]];
+            orientation(self.points[a], self.points[b], self.points[c]) != Orientation::Clockwise
+        });
+        if convex { Some(piece) } else { None }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn polygon(points: &[(i64, i64)]) -> Polygon {
+        Polygon::new(points.iter().map(|&(x, y)| Vec2::new(Fix::new(x), Fix::new(y))).collect())
+    }
+
+    // A comb with three teeth, with a collinear vertex on the bottom edge
+    fn comb() -> Polygon {
+        polygon(&[
+            (0, 0), (3, 0), (6, 0), (6, 4), (5, 4), (5, 1), (4, 1),
+            (4, 4), (2, 4), (2, 1), (1, 1), (1, 4), (0, 4),
+        ])
+    }
+
+    fn assert_triangulation(shape: &Polygon) {
+        let triangles = shape.triangulate();
+        let mut area = Fix::ZERO;
+        for triangle in triangles.iter() {
+            let piece = Polygon::new(triangle.iter().map(|&i| shape.points[i]).collect());
+            assert_eq!(piece.winding(), Orientation::CounterClockwise, "{:?}", triangle);
+            assert!(shape.contains_point(piece.centroid().unwrap()), "{:?}", triangle);
+            area += piece.area();
+        }
+        assert_eq!(area, shape.area());
+    }
+
+    #[test]
+    fn triangulates_either_winding() {
+        let square = polygon(&[(0, 0), (2, 0), (2, 2), (0, 2)]);
+        assert_eq!(square.triangulate().len(), 2);
+        assert_triangulation(&square);
+
+        let mut shape = comb();
+        assert_triangulation(&shape);
+        shape.reverse();
+        assert_eq!(shape.winding(), Orientation::Clockwise);
+        assert_triangulation(&shape);
+        assert!(shape.triangulate().len() <= shape.points.len() - 2);
+    }
+
+    #[test]
+    fn triangulating_degenerate_input_terminates() {
+        assert!(polygon(&[(0, 0), (1, 1), (2, 2), (3, 3)]).triangulate().is_empty());
+        assert!(polygon(&[(0, 0), (1, 0)]).triangulate().is_empty());
+        // Bow tie, crossing itself in the middle
+        let bow_tie = polygon(&[(0, 0), (2, 2), (2, 0), (0, 2)]);
+        assert!(bow_tie.triangulate().len() <= 2);
+    }
+
+    #[test]
+    fn convex_hull_is_counterclockwise_and_strict() {
+        let mut seed = 0x6a09_e667_f3bc_c908_u64;
+        let mut points = Vec::new();
+        for _ in 0..200 {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            let (x, y) = ((seed as i32 as i64) << 4, (seed >> 32) as i32 as i64);
+            points.push(Vec2::new(Fix::from_bits(x), Fix::from_bits(y)));
+        }
+        points.extend_from_slice(&points.clone()[..20]);
+
+        let hull = Polygon::convex_hull(&points);
+        assert!(hull.is_convex());
+        let count = hull.points.len();
+        for i in 0..count {
+            let turn = orientation(hull.points[i], hull.points[(i + 1) % count], hull.points[(i + 2) % count]);
+            assert_eq!(turn, Orientation::CounterClockwise);
+        }
+        assert!(points.iter().all(|&point| hull.contains_point(point)));
+        let lowest = points.iter().min_by_key(|point| (point.x, point.y)).unwrap();
+        assert_eq!(hull.points[0], *lowest);
+
+        // Order of the input does not matter
+        points.reverse();
+        assert_eq!(Polygon::convex_hull(&points), hull);
+    }
+
+    #[test]
+    fn convex_hull_drops_collinear_and_repeated_points() {
+        let square = polygon(&[(0, 0), (1, 0), (2, 0), (2, 2), (0, 2), (1, 1), (2, 2), (0, 1)]);
+        assert_eq!(Polygon::convex_hull(&square.points), polygon(&[(0, 0), (2, 0), (2, 2), (0, 2)]));
+        let line = polygon(&[(3, 3), (0, 0), (1, 1), (2, 2)]);
+        assert_eq!(Polygon::convex_hull(&line.points), polygon(&[(0, 0), (3, 3)]));
+        assert_eq!(Polygon::convex_hull(&[]), Polygon::new(Vec::new()));
+    }
+
+    #[test]
+    fn convex_decomposition_covers_the_polygon() {
+        let shape = comb();
+        let pieces = shape.convex_decomposition();
+        assert!(pieces.len() >= 4 && pieces.len() < shape.triangulate().len());
+        let mut area = Fix::ZERO;
+        for piece in pieces.iter() {
+            assert!(piece.is_convex());
+            assert_eq!(piece.winding(), Orientation::CounterClockwise);
+            area += piece.area();
+        }
+        assert_eq!(area, shape.area());
+    }
+
+    #[test]
+    fn area_centroid_and_containment() {
+        let shape = comb();
+        assert_eq!(shape.signed_area(), Fix::new(18));
+        let square = polygon(&[(0, 0), (0, 2), (2, 2), (2, 0)]);
+        assert_eq!(square.signed_area(), Fix::new(-4));
+        assert_eq!(square.centroid(), Some(Vec2::new(Fix::ONE, Fix::ONE)));
+        assert_eq!(polygon(&[(0, 0), (1, 1), (2, 2)]).centroid(), None);
+        assert!(shape.contains_point(Vec2::new(Fix::new(5), Fix::new(4))));
+        assert!(shape.contains_point(Vec2::new(Fix::new(3), Fix::new(1))));
+        assert!(!shape.contains_point(Vec2::new(Fix::new(3) / Fix::TWO, Fix::new(2))));
+        assert!(!shape.is_convex() && square.is_convex());
+    }
+}
@@ -0,0 +1,125 @@
+use std::ops;
+
+use super::angle::Angle;
+use super::fix::Fix;
+use super::mat2::Mat2;
+use super::vec2::Vec2;
+
+// Rotation as a unit complex number cos + i sin, so applying it needs no
+// trigonometry and composing is a complex multiplication
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Rotation {
+    cos: Fix,
+    sin: Fix,
+}
+
+impl Rotation {
+    pub const IDENTITY: Rotation = Rotation { cos: Fix::ONE, sin: Fix::ZERO };
+
+    // Counterclockwise by the angle in radians
+    pub fn from_angle(angle: Fix) -> Rotation {
+        let (sin, cos) = angle.sin_cos();
+        Rotation { cos, sin }
+    }
+
+    // Angle in radians from atan2, in (-PI, PI]
+    pub fn to_angle(self) -> Fix {
+        Fix::atan2(self.sin, self.cos)
+    }
+
+    // Rotation taking the x axis to the direction of the vector, identity
+    // for the zero vector
+    pub fn from_direction(direction: Vec2) -> Rotation {
+        let unit = direction.normalize();
+        if unit == Vec2::ZERO {
+            return Rotation::IDENTITY;
+        }
+        Rotation { cos: unit.x, sin: unit.y }
+    }
+
+    pub fn cos(self) -> Fix {
+        self.cos
+    }
+
+    pub fn sin(self) -> Fix {
+        self.sin
+    }
+
+    pub fn inverse(self) -> Rotation {
+        Rotation { cos: self.cos, sin: -self.sin }
+    }
+
+    // Exact rescaling to unit length
+    pub fn normalize(self) -> Rotation {
+        Rotation::from_direction(Vec2::new(self.cos, self.sin))
+    }
+
+    pub fn rotate(self, vector: Vec2) -> Vec2 {
+        Vec2::new(
+            self.cos * vector.x - self.sin * vector.y,
+            self.sin * vector.x + self.cos * vector.y,
+        )
+    }
+
+    // Interpolates the angle, so the speed is constant
+    pub fn slerp(self, other: Rotation, t: Fix) -> Rotation {
+        let turn = (other * self.inverse()).to_angle();
+        self * Rotation::from_angle(turn * t)
+    }
+
+    // Normalized linear interpolation of the pairs. Cheaper than slerp, but
+    // the speed varies along the arc. Opposite rotations have no shorter
+    // side and fall back to slerp.
+    pub fn nlerp(self, other: Rotation, t: Fix) -> Rotation {
+        let pair = Vec2::new(self.cos, self.sin).lerp(Vec2::new(other.cos, other.sin), t);
+        if pair == Vec2::ZERO {
+            return self.slerp(other, t);
+        }
+        Rotation::from_direction(pair)
+    }
+
+    pub fn to_mat2(self) -> Mat2 {
+        Mat2::new(self.cos, -self.sin, self.sin, self.cos)
+    }
+}
+
+impl From<Angle> for Rotation {
+    fn from(angle: Angle) -> Rotation {
+        let (sin, cos) = angle.sin_cos();
+        Rotation { cos, sin }
+    }
+}
+
+impl From<Rotation> for Mat2 {
+    fn from(rotation: Rotation) -> Mat2 {
+        rotation.to_mat2()
+    }
+}
+
+// Composition, applying the right hand side first. The product is pulled
+// back towards unit length with one Newton step, scaling by
+// (3 - |r|^2) / 2, so long chains of compositions do not drift.
+impl ops::Mul for Rotation {
+    type Output = Rotation;
+
+    fn mul(self, _rhs: Rotation) -> Rotation {
+        let cos = self.cos * _rhs.cos - self.sin * _rhs.sin;
+        let sin = self.sin * _rhs.cos + self.cos * _rhs.sin;
+        let scale = (Fix::new(3) - (cos * cos + sin * sin)) * Fix::HALF;
+        Rotation { cos: cos * scale, sin: sin * scale }
+    }
+}
+
+impl ops::MulAssign for Rotation {
+    fn mul_assign(&mut self, _rhs: Rotation) {
+        *self = *self * _rhs;
+    }
+}
+
+impl ops::Mul<Vec2> for Rotation {
+    type Output = Vec2;
+
+    fn mul(self, _rhs: Vec2) -> Vec2 {
+        self.rotate(_rhs)
+    }
+}
@@ -0,0 +1,261 @@
+use std::fmt;
+use std::ops;
+
+use super::fix::Fix;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Vec2 {
+    pub x: Fix,
+    pub y: Fix,
+}
+
+impl Vec2 {
+    pub const ZERO: Vec2 = Vec2 { x: Fix::ZERO, y: Fix::ZERO };
+    pub const ONE: Vec2 = Vec2 { x: Fix::ONE, y: Fix::ONE };
+    pub const UNIT_X: Vec2 = Vec2 { x: Fix::ONE, y: Fix::ZERO };
+    pub const UNIT_Y: Vec2 = Vec2 { x: Fix::ZERO, y: Fix::ONE };
+
+    pub fn new(x: Fix, y: Fix) -> Vec2 {
+        Vec2 { x, y }
+    }
+
+    // Unit vector pointing at the angle in radians, counterclockwise from
+    // the x axis
+    pub fn from_angle(angle: Fix) -> Vec2 {
+        let (sin, cos) = angle.sin_cos();
+        Vec2 { x: cos, y: sin }
+    }
+
+    // Angle from the x axis in radians, zero for the zero vector
+    pub fn angle(self) -> Fix {
+        Fix::atan2(self.y, self.x)
+    }
+
+    pub fn dot(self, other: Vec2) -> Fix {
+        self.x * other.x + self.y * other.y
+    }
+
+    // z component of the 3D cross product, positive when other is
+    // counterclockwise from self
+    pub fn cross(self, other: Vec2) -> Fix {
+        self.x * other.y - self.y * other.x
+    }
+
+    // Rotated a quarter turn counterclockwise
+    pub fn perp(self) -> Vec2 {
+        Vec2 { x: -self.y, y: self.x }
+    }
+
+    // Exact sum of squares of the raw values, which has 60 fractional bits.
+    // Never overflows, so it is safe for comparing distances between far
+    // away coordinates.
+    pub fn length_squared_wide(self) -> u128 {
+        let x = self.x.to_bits().unsigned_abs() as u128;
+        let y = self.y.to_bits().unsigned_abs() as u128;
+        x * x + y * y
+    }
+
+    // Saturates to MAX when the square does not fit in Fix
+    pub fn length_squared(self) -> Fix {
+        let wide = (self.length_squared_wide() + (1 << 29)) >> 30;
+        Fix::from_bits(wide.min(Fix::MAX.to_bits() as u128) as i64)
+    }
+
+    // Computed from the exact sum of squares, so it is correctly rounded
+    // and only saturates when the length itself does not fit in Fix
+    pub fn length(self) -> Fix {
+        Fix::sqrt_wide(self.length_squared_wide())
+    }
+
+    pub fn distance(self, other: Vec2) -> Fix {
+        (other - self).length()
+    }
+
+    pub fn distance_squared(self, other: Vec2) -> Fix {
+        (other - self).length_squared()
+    }
+
+    // Unit vector in the same direction, or zero for the zero vector. The
+    // components are scaled to around 40 significant bits first, so tiny
+    // and huge vectors keep their direction.
+    pub fn normalize(self) -> Vec2 {
+        let largest = self.x.to_bits().unsigned_abs().max(self.y.to_bits().unsigned_abs());
+        if largest == 0 {
+            return Vec2::ZERO;
+        }
+        let shift = (64 - largest.leading_zeros() as i32) - 40;
+        let scale = |value: Fix| {
+            let bits = value.to_bits() as i128;
+            if shift > 0 { bits >> shift } else { bits << -shift }
+        };
+        let (x, y) = (scale(self.x), scale(self.y));
+        let length = Fix::sqrt_wide((x * x + y * y) as u128).to_bits() as i128;
+        let divide = |value: i128| {
+            let scaled = value << 30;
+            let rounded = if scaled < 0 { scaled - length / 2 } else { scaled + length / 2 };
+            Fix::from_bits((rounded / length) as i64)
+        };
+        Vec2 { x: divide(x), y: divide(y) }
+    }
+
+    // Rotated counterclockwise by the angle in radians
+    pub fn rotate(self, angle: Fix) -> Vec2 {
+        let (sin, cos) = angle.sin_cos();
+        Vec2 {
+            x: self.x * cos - self.y * sin,
+            y: self.x * sin + self.y * cos,
+        }
+    }
+
+    // Mirrored across the line with the given unit normal
+    pub fn reflect(self, normal: Vec2) -> Vec2 {
+        self - normal * (self.dot(normal) * Fix::TWO)
+    }
+
+    // Component along other, zero when other is the zero vector
+    pub fn project(self, other: Vec2) -> Vec2 {
+        let length_squared = other.length_squared();
+        if length_squared == Fix::ZERO {
+            return Vec2::ZERO;
+        }
+        other * (self.dot(other) / length_squared)
+    }
+
+    // t outside [0, 1] extrapolates
+    pub fn lerp(self, other: Vec2, t: Fix) -> Vec2 {
+        self + (other - self) * t
+    }
+
+    pub fn abs(self) -> Vec2 {
+        Vec2 { x: Fix::abs(self.x), y: Fix::abs(self.y) }
+    }
+
+    pub fn min(self, other: Vec2) -> Vec2 {
+        Vec2 { x: self.x.min(other.x), y: self.y.min(other.y) }
+    }
+
+    pub fn max(self, other: Vec2) -> Vec2 {
+        Vec2 { x: self.x.max(other.x), y: self.y.max(other.y) }
+    }
+}
+
+impl fmt::Display for Vec2 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl ops::Add for Vec2 {
+    type Output = Vec2;
+
+    #[inline(always)]
+    fn add(self, _rhs: Vec2) -> Vec2 {
+        Vec2 { x: self.x + _rhs.x, y: self.y + _rhs.y }
+    }
+}
+
+impl ops::AddAssign for Vec2 {
+    #[inline(always)]
+    fn add_assign(&mut self, _rhs: Vec2) {
+        *self = *self + _rhs;
+    }
+}
+
+impl ops::Sub for Vec2 {
+    type Output = Vec2;
+
+    #[inline(always)]
+    fn sub(self, _rhs: Vec2) -> Vec2 {
+        Vec2 { x: self.x - _rhs.x, y: self.y - _rhs.y }
+    }
+}
+
+impl ops::SubAssign for Vec2 {
+    #[inline(always)]
+    fn sub_assign(&mut self, _rhs: Vec2) {
+        *self = *self - _rhs;
+    }
+}
+
+impl ops::Neg for Vec2 {
+    type Output = Vec2;
+
+    #[inline(always)]
+    fn neg(self) -> Vec2 {
+        Vec2 { x: -self.x, y: -self.y }
+    }
+}
+
+// Component-wise product and quotient
+impl ops::Mul for Vec2 {
+    type Output = Vec2;
+
+    #[inline(always)]
+    fn mul(self, _rhs: Vec2) -> Vec2 {
+        Vec2 { x: self.x * _rhs.x, y: self.y * _rhs.y }
+    }
+}
+
+impl ops::MulAssign for Vec2 {
+    #[inline(always)]
+    fn mul_assign(&mut self, _rhs: Vec2) {
+        *self = *self * _rhs;
+    }
+}
+
+impl ops::Div for Vec2 {
+    type Output = Vec2;
+
+    #[inline(always)]
+    fn div(self, _rhs: Vec2) -> Vec2 {
+        Vec2 { x: self.x / _rhs.x, y: self.y / _rhs.y }
+    }
+}
+
+impl ops::DivAssign for Vec2 {
+    #[inline(always)]
+    fn div_assign(&mut self, _rhs: Vec2) {
+        *self = *self / _rhs;
+    }
+}
+
+impl ops::Mul<Fix> for Vec2 {
+    type Output = Vec2;
+
+    #[inline(always)]
+    fn mul(self, _rhs: Fix) -> Vec2 {
+        Vec2 { x: self.x * _rhs, y: self.y * _rhs }
+    }
+}
+
+impl ops::MulAssign<Fix> for Vec2 {
+    #[inline(always)]
+    fn mul_assign(&mut self, _rhs: Fix) {
+        *self = *self * _rhs;
+    }
+}
+
+impl ops::Mul<Vec2> for Fix {
+    type Output = Vec2;
+
+    #[inline(always)]
+    fn mul(self, _rhs: Vec2) -> Vec2 {
+        _rhs * self
+    }
+}
+
+impl ops::Div<Fix> for Vec2 {
+    type Output = Vec2;
+
+    #[inline(always)]
+    fn div(self, _rhs: Fix) -> Vec2 {
+        Vec2 { x: self.x / _rhs, y: self.y / _rhs }
+    }
+}
+
+impl ops::DivAssign<Fix> for Vec2 {
+    #[inline(always)]
+    fn div_assign(&mut self, _rhs: Fix) {
+        *self = *self / _rhs;
+    }
+}
@@ -0,0 +1,195 @@
+use std::cmp::Ordering;
+use std::ops;
+
+// Signed 256 bit integer for exact matrix inverses and complex division,
+// where the intermediate values outgrow i128. Only what those need is
+// implemented: sums, products and quotients.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub(crate) struct I256 {
+    negative: bool,
+    // Magnitude, least significant limb first
+    limbs: [u64; 4],
+}
+
+impl I256 {
+    pub(crate) const ZERO: I256 = I256 { negative: false, limbs: [0; 4] };
+
+    pub(crate) fn from_i128(value: i128) -> I256 {
+        let magnitude = value.unsigned_abs();
+        I256 {
+            negative: value < 0,
+            limbs: [magnitude as u64, (magnitude >> 64) as u64, 0, 0],
+        }
+    }
+
+    pub(crate) fn is_zero(&self) -> bool {
+        self.limbs == [0; 4]
+    }
+
+    pub(crate) fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    pub(crate) fn shl(self, bits: u32) -> I256 {
+        let mut limbs = [0u64; 4];
+        let (skip, shift) = ((bits / 64) as usize, bits % 64);
+        for i in (skip..4).rev() {
+            let mut limb = self.limbs[i - skip] << shift;
+            if shift > 0 && i > skip {
+                limb |= self.limbs[i - skip - 1] >> (64 - shift);
+            }
+            limbs[i] = limb;
+        }
+        I256 { negative: self.negative, limbs }
+    }
+
+    // Quotient rounded half away from zero, None for a zero divisor or a
+    // result outside i64
+    pub(crate) fn div_round(self, divisor: I256) -> Option<i64> {
+        if divisor.is_zero() {
+            return None;
+        }
+        let (mut quotient, remainder) = self.div_magnitude(divisor);
+        if compare(&shl_one(remainder, 0), &divisor.limbs) != Ordering::Less {
+            quotient = add_magnitude(quotient, [1, 0, 0, 0]);
+        }
+        match signed_quotient(quotient, self.negative != divisor.negative) {
+            (value, false) => Some(value),
+            _ => None,
+        }
+    }
+
+    // Quotient rounded towards negative infinity, None for a zero divisor
+    // or a result outside i64
+    pub(crate) fn div_floor(self, divisor: I256) -> Option<i64> {
+        if divisor.is_zero() {
+            return None;
+        }
+        let (mut quotient, remainder) = self.div_magnitude(divisor);
+        let negative = self.negative != divisor.negative;
+        if negative && remainder != [0; 4] {
+            quotient = add_magnitude(quotient, [1, 0, 0, 0]);
+        }
+        match signed_quotient(quotient, negative) {
+            (value, false) => Some(value),
+            _ => None,
+        }
+    }
+
+    // Quotient truncated towards zero like integer division, as the low 64
+    // bits and whether it overflowed i64. Panics on a zero divisor.
+    pub(crate) fn overflowing_div(self, divisor: I256) -> (i64, bool) {
+        if divisor.is_zero() {
+            panic!("attempt to divide by zero");
+        }
+        let (quotient, _) = self.div_magnitude(divisor);
+        signed_quotient(quotient, self.negative != divisor.negative)
+    }
+
+    fn div_magnitude(self, divisor: I256) -> ([u64; 4], [u64; 4]) {
+        let mut quotient = [0u64; 4];
+        let mut remainder = [0u64; 4];
+        for bit in (0..256).rev() {
+            remainder = shl_one(remainder, (self.limbs[bit / 64] >> (bit % 64)) & 1);
+            if compare(&remainder, &divisor.limbs) != Ordering::Less {
+                remainder = sub_magnitude(remainder, divisor.limbs);
+                quotient[bit / 64] |= 1 << (bit % 64);
+            }
+        }
+        (quotient, remainder)
+    }
+}
+
+fn signed_quotient(magnitude: [u64; 4], negative: bool) -> (i64, bool) {
+    let low = magnitude[0] as i64;
+    let value = if negative { low.wrapping_neg() } else { low };
+    let fits = magnitude[1..] == [0; 3] && (value < 0) == (negative && low != 0);
+    (value, !fits)
+}
+
+fn compare(a: &[u64; 4], b: &[u64; 4]) -> Ordering {
+    a.iter().rev().cmp(b.iter().rev())
+}
+
+fn shl_one(limbs: [u64; 4], low_bit: u64) -> [u64; 4] {
+    [
+        (limbs[0] << 1) | low_bit,
+        (limbs[1] << 1) | (limbs[0] >> 63),
+        (limbs[2] << 1) | (limbs[1] >> 63),
+        (limbs[3] << 1) | (limbs[2] >> 63),
+    ]
+}
+
+fn add_magnitude(a: [u64; 4], b: [u64; 4]) -> [u64; 4] {
+    let mut result = [0u64; 4];
+    let mut carry = 0u128;
+    for i in 0..4 {
+        let sum = a[i] as u128 + b[i] as u128 + carry;
+        result[i] = sum as u64;
+        carry = sum >> 64;
+    }
+    result
+}
+
+// a - b for a >= b
+fn sub_magnitude(a: [u64; 4], b: [u64; 4]) -> [u64; 4] {
+    let mut result = [0u64; 4];
+    let mut borrow = 0i128;
+    for i in 0..4 {
+        let difference = a[i] as i128 - b[i] as i128 - borrow;
+        result[i] = difference as u64;
+        borrow = (difference < 0) as i128;
+    }
+    result
+}
+
+impl ops::Add for I256 {
+    type Output = I256;
+
+    fn add(self, _rhs: I256) -> I256 {
+        if self.negative == _rhs.negative {
+            return I256 { negative: self.negative, limbs: add_magnitude(self.limbs, _rhs.limbs) };
+        }
+        let (larger, smaller) = match compare(&self.limbs, &_rhs.limbs) {
+            Ordering::Less => (_rhs, self),
+            _ => (self, _rhs),
+        };
+        let limbs = sub_magnitude(larger.limbs, smaller.limbs);
+        I256 { negative: larger.negative && limbs != [0; 4], limbs }
+    }
+}
+
+impl ops::Neg for I256 {
+    type Output = I256;
+
+    fn neg(self) -> I256 {
+        I256 { negative: !self.negative && !self.is_zero(), limbs: self.limbs }
+    }
+}
+
+impl ops::Sub for I256 {
+    type Output = I256;
+
+    fn sub(self, _rhs: I256) -> I256 {
+        self + -_rhs
+    }
+}
+
+// Truncated to 256 bits, which the callers never exceed
+impl ops::Mul for I256 {
+    type Output = I256;
+
+    fn mul(self, _rhs: I256) -> I256 {
+        let mut limbs = [0u64; 4];
+        for i in 0..4 {
+            let mut carry = 0u128;
+            for j in 0..4 - i {
+                let product = self.limbs[i] as u128 * _rhs.limbs[j] as u128 + limbs[i + j] as u128 + carry;
+                limbs[i + j] = product as u64;
+                carry = product >> 64;
+            }
+        }
+        let negative = self.negative != _rhs.negative && limbs != [0; 4];
+        I256 { negative, limbs }
+    }
+}
@@ -1,8 +1,8 @@
-mod dmath;
-use dmath::fix::Fix;
+use lion2d::dmath;
+use lion2d::dmath::fix::Fix;
 
 fn main() {
     let one = Fix::ONE;
     println!("one is {}", one);
     dmath::fix::testfun();
-}
\ No newline at end of file
+}